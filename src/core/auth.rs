@@ -0,0 +1,187 @@
+//! Upstream authentication: how generated servers authenticate to the API.
+//!
+//! Each security scheme under `components/securitySchemes` becomes one
+//! [`AuthScheme`] telling the generated server which credentials it takes and
+//! where they go in a request. Credentials are configured per scheme, in the
+//! server config or through environment variables starting with the scheme's
+//! `env_prefix`, e.g. `PETSTORE_AUTH_CLIENT_ID`.
+
+// Internal imports (std, crate)
+use std::collections::BTreeMap;
+
+use crate::core::diagnostics::{Diagnostic, pointer_join};
+use crate::core::templates::ParameterKind;
+use crate::core::utils::to_snake_case;
+
+// External imports (alphabetized)
+use openapiv3::{APIKeyLocation, SecurityScheme};
+use serde::{Deserialize, Serialize};
+
+/// The credentials a scheme takes, and how they are sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthKind {
+    /// A key sent as a header, query parameter or cookie
+    ApiKey,
+    /// HTTP basic authentication with a username and password
+    Basic,
+    /// A bearer token in the `Authorization` header
+    Bearer,
+    /// OAuth2 client credentials, exchanged for bearer tokens at `token_url`
+    ClientCredentials,
+}
+
+/// A security scheme generated servers can authenticate with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthScheme {
+    /// Name under `components/securitySchemes`, as used by security requirements
+    pub name: String,
+    /// Upper snake case prefix of the environment variables holding credentials
+    pub env_prefix: String,
+    pub kind: AuthKind,
+    /// Where an API key goes
+    pub location: Option<ParameterKind>,
+    /// Header, query parameter or cookie name of an API key
+    pub parameter_name: Option<String>,
+    /// Token endpoint of the OAuth2 client credentials flow
+    pub token_url: Option<String>,
+    /// Scopes declared by the OAuth2 flow
+    pub scopes: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// Map parsed security schemes to the ones generated servers support.
+///
+/// OAuth2 schemes without a client credentials flow and OpenID Connect schemes
+/// take a ready-made bearer token. HTTP schemes other than `basic` and `bearer`
+/// are left out and reported.
+pub fn collect_auth_schemes(
+    schemes: &BTreeMap<String, SecurityScheme>,
+) -> (Vec<AuthScheme>, Vec<Diagnostic>) {
+    let mut auth_schemes = Vec::new();
+    let mut diagnostics = Vec::new();
+    for (name, scheme) in schemes.clone() {
+        let mut auth = AuthScheme {
+            env_prefix: to_snake_case(&name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"))
+                .to_uppercase(),
+            name,
+            kind: AuthKind::Bearer,
+            location: None,
+            parameter_name: None,
+            token_url: None,
+            scopes: Vec::new(),
+            description: None,
+        };
+        match scheme {
+            SecurityScheme::APIKey {
+                location,
+                name,
+                description,
+                ..
+            } => {
+                auth.kind = AuthKind::ApiKey;
+                auth.location = Some(match location {
+                    APIKeyLocation::Query => ParameterKind::Query,
+                    APIKeyLocation::Header => ParameterKind::Header,
+                    APIKeyLocation::Cookie => ParameterKind::Cookie,
+                });
+                auth.parameter_name = Some(name);
+                auth.description = description;
+            }
+            SecurityScheme::HTTP {
+                scheme,
+                description,
+                ..
+            } => {
+                auth.kind = match scheme.to_ascii_lowercase().as_str() {
+                    "basic" => AuthKind::Basic,
+                    "bearer" => AuthKind::Bearer,
+                    _ => {
+                        diagnostics.push(Diagnostic::warning(
+                            pointer_join("/components/securitySchemes", &auth.name),
+                            format!(
+                                "HTTP authentication scheme '{scheme}' is not supported; \
+                                 requests are sent without it"
+                            ),
+                        ));
+                        continue;
+                    }
+                };
+                auth.description = description;
+            }
+            SecurityScheme::OAuth2 {
+                flows, description, ..
+            } => {
+                if let Some(flow) = flows.client_credentials {
+                    auth.kind = AuthKind::ClientCredentials;
+                    auth.token_url = Some(flow.token_url);
+                    auth.scopes = flow.scopes.into_keys().collect();
+                }
+                auth.description = description;
+            }
+            SecurityScheme::OpenIDConnect { description, .. } => {
+                auth.description = description;
+            }
+        }
+        auth_schemes.push(auth);
+    }
+    (auth_schemes, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::openapi::OpenApiContext;
+    use serde_json::json;
+
+    #[test]
+    fn test_collect_auth_schemes() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "paths": {},
+                "components": {"securitySchemes": {
+                    "api_key": {"type": "apiKey", "name": "session", "in": "cookie"},
+                    "basicAuth": {"type": "http", "scheme": "Basic"},
+                    "digest": {"type": "http", "scheme": "digest"},
+                    "oidc": {"type": "openIdConnect", "openIdConnectUrl": "https://id.example.com"},
+                    "petstore_auth": {"type": "oauth2", "flows": {
+                        "implicit": {"authorizationUrl": "https://example.com/authorize", "scopes": {}},
+                        "clientCredentials": {
+                            "tokenUrl": "https://example.com/token",
+                            "scopes": {"write:pets": "", "read:pets": ""}
+                        }
+                    }}
+                }}
+            }),
+        };
+        let (schemes, diagnostics) = collect_auth_schemes(&spec.security_schemes().0);
+        let summary: Vec<(&str, &str, AuthKind)> = schemes
+            .iter()
+            .map(|s| (s.name.as_str(), s.env_prefix.as_str(), s.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("api_key", "API_KEY", AuthKind::ApiKey),
+                ("basicAuth", "BASIC_AUTH", AuthKind::Basic),
+                ("oidc", "OIDC", AuthKind::Bearer),
+                (
+                    "petstore_auth",
+                    "PETSTORE_AUTH",
+                    AuthKind::ClientCredentials
+                )
+            ]
+        );
+        assert!(matches!(schemes[0].location, Some(ParameterKind::Cookie)));
+        assert_eq!(schemes[0].parameter_name.as_deref(), Some("session"));
+        assert_eq!(
+            schemes[3].token_url.as_deref(),
+            Some("https://example.com/token")
+        );
+        assert_eq!(schemes[3].scopes, ["read:pets", "write:pets"]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pointer, "/components/securitySchemes/digest");
+    }
+}
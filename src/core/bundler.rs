@@ -0,0 +1,796 @@
+//! Bundling of OpenAPI specifications split across several documents.
+//!
+//! A spec may reference other files or URLs (`./schemas/pet.yaml#/Pet`,
+//! `https://example.com/common.json#/components/responses/NotFound`). The
+//! bundler loads every document reachable from the root, copies each
+//! externally referenced value into the matching `components` section of the
+//! root document and rewrites the reference to point at the local copy. The
+//! result is a single self-contained document whose `$ref`s are all local and
+//! known to resolve.
+//!
+//! [`bundle_lenient`] does the same for `agenterra validate`, but leaves any
+//! reference it cannot resolve in place and reports it as a [`Diagnostic`]
+//! instead of failing, so the rest of the spec can still be checked.
+
+// Internal imports (std, crate)
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use crate::core::Error;
+use crate::core::diagnostics::{Diagnostic, pointer_join};
+use crate::core::error::Result;
+use crate::core::openapi::OpenApiContext;
+
+// External imports (alphabetized)
+use serde_json::{Map as JsonMap, Value as JsonValue};
+use tokio::fs;
+use url::Url;
+
+/// Keys whose values are JSON schemas; a `$ref` below any of them refers to a schema
+const SCHEMA_KEYS: &[&str] = &[
+    "schema",
+    "schemas",
+    "definitions",
+    "$defs",
+    "properties",
+    "patternProperties",
+    "additionalProperties",
+    "items",
+    "prefixItems",
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+];
+
+/// Keys holding named maps of objects that share their name with a `components` section
+const COMPONENT_MAPS: &[&str] = &[
+    "responses",
+    "headers",
+    "examples",
+    "links",
+    "callbacks",
+    "securitySchemes",
+];
+
+/// Load the spec at `location` (a file path or http(s) URL) and bundle every
+/// document it references into one resolved document.
+pub async fn bundle(location: &str) -> Result<JsonValue> {
+    let mut bundler = Bundler::load(location, false).await?;
+    bundler.bundle_root()
+}
+
+/// Like [`bundle`], but leave every `$ref` that cannot be resolved as it is and
+/// report it as an error at its place in the bundled document. Only the root
+/// document failing to load is an error.
+pub async fn bundle_lenient(location: &str) -> Result<(JsonValue, Vec<Diagnostic>)> {
+    let mut bundler = Bundler::load(location, true).await?;
+    let root = bundler.bundle_root()?;
+    Ok((root, bundler.diagnostics))
+}
+
+/// Decode a `$ref` fragment into a JSON pointer usable with `serde_json::Value::pointer`.
+///
+/// Fragments are URI-encoded (`%7BpetId%7D`); `~0`/`~1` escapes are left for the
+/// pointer lookup to interpret.
+pub fn fragment_to_pointer(fragment: &str) -> String {
+    let bytes = fragment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = bytes.get(i + 1..i + 3)
+            && let Ok(hex) = std::str::from_utf8(hex)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Turn a CLI location into an absolute URL
+fn location_url(location: &str) -> Result<Url> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return Url::parse(location)
+            .map_err(|e| Error::openapi(format!("Invalid spec URL '{location}': {e}")));
+    }
+    let path = std::fs::canonicalize(location)?;
+    Url::from_file_path(&path)
+        .map_err(|_| Error::openapi(format!("Cannot build a file URL for '{}'", path.display())))
+}
+
+/// The document part of a URL, used as the key of loaded documents
+fn document_url(url: &Url) -> Url {
+    let mut doc = url.clone();
+    doc.set_fragment(None);
+    doc
+}
+
+/// Documents keyed by URL, and when `lenient`, why referenced documents failed to load
+type Documents = (HashMap<Url, JsonValue>, HashMap<Url, String>);
+
+/// Load the root document and, transitively, every document it references.
+///
+/// When `lenient`, a referenced document that fails to load is recorded
+/// instead, and references that are not valid URLs are skipped; bundling
+/// reports the references concerned.
+async fn load_documents(root: &Url, lenient: bool) -> Result<Documents> {
+    let mut documents = HashMap::new();
+    let mut unloaded = HashMap::new();
+    documents.insert(root.clone(), fetch(root).await?);
+
+    let mut queue = VecDeque::from([root.clone()]);
+    while let Some(base) = queue.pop_front() {
+        let mut refs = Vec::new();
+        collect_refs(&documents[&base], &mut Vec::new(), &mut refs);
+
+        for (ref_str, path) in refs {
+            let target = match base.join(&ref_str) {
+                Ok(target) => target,
+                Err(_) if lenient => continue,
+                Err(e) => {
+                    let reason = format!("invalid reference ({e})");
+                    return Err(unresolved(&ref_str, &base, &path, &reason));
+                }
+            };
+            let doc = document_url(&target);
+            if documents.contains_key(&doc) || unloaded.contains_key(&doc) {
+                continue;
+            }
+            match fetch(&doc).await {
+                Ok(value) => {
+                    documents.insert(doc.clone(), value);
+                    queue.push_back(doc);
+                }
+                Err(e) if lenient => {
+                    unloaded.insert(doc, e.to_string());
+                }
+                Err(e) => return Err(unresolved(&ref_str, &base, &path, &e.to_string())),
+            }
+        }
+    }
+    Ok((documents, unloaded))
+}
+
+/// Read and parse a single JSON or YAML document
+async fn fetch(url: &Url) -> Result<JsonValue> {
+    let content = match url.scheme() {
+        "file" => {
+            let path = url
+                .to_file_path()
+                .map_err(|_| Error::openapi(format!("Invalid file URL '{url}'")))?;
+            read_file(&path).await?
+        }
+        "http" | "https" => {
+            let response = reqwest::get(url.as_str())
+                .await
+                .map_err(|e| Error::openapi(format!("Failed to fetch {url}: {e}")))?;
+            if !response.status().is_success() {
+                return Err(Error::openapi(format!(
+                    "Failed to fetch {url}: HTTP {}",
+                    response.status()
+                )));
+            }
+            response
+                .text()
+                .await
+                .map_err(|e| Error::openapi(format!("Failed to read {url}: {e}")))?
+        }
+        scheme => {
+            return Err(Error::openapi(format!(
+                "Unsupported reference scheme '{scheme}' in {url}"
+            )));
+        }
+    };
+    OpenApiContext::parse_content(&content)
+        .map(|ctx| ctx.json)
+        .map_err(|e| Error::openapi(format!("Failed to parse {url}: {e}")))
+}
+
+async fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .await
+        .map_err(|e| Error::openapi(format!("Failed to read {}: {e}", path.display())))
+}
+
+/// Collect every `$ref` string in `value` together with its JSON path
+fn collect_refs(value: &JsonValue, path: &mut Vec<String>, out: &mut Vec<(String, Vec<String>)>) {
+    match value {
+        JsonValue::Object(map) => {
+            if let Some(ref_str) = map.get("$ref").and_then(JsonValue::as_str) {
+                out.push((ref_str.to_string(), path.clone()));
+            }
+            for (key, child) in map {
+                path.push(key.clone());
+                collect_refs(child, path, out);
+                path.pop();
+            }
+        }
+        JsonValue::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                path.push(i.to_string());
+                collect_refs(child, path, out);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Render a JSON path as a pointer for error messages (e.g. `#/paths/~1pets/get`)
+fn display_path(path: &[String]) -> String {
+    let segments: Vec<String> = path
+        .iter()
+        .map(|s| s.replace('~', "~0").replace('/', "~1"))
+        .collect();
+    format!("#/{}", segments.join("/"))
+}
+
+fn unresolved(ref_str: &str, origin: &Url, path: &[String], reason: &str) -> Error {
+    Error::openapi(format!(
+        "Unresolved $ref '{ref_str}' in {origin} at {}: {reason}",
+        display_path(path)
+    ))
+}
+
+/// Where an external value is placed in the bundled document
+enum Placement {
+    /// Copied into `components/<kind>/<name>`
+    Component { kind: String, name: String },
+    /// Inlined in place (path items have no component section in OpenAPI 3.0)
+    Inline,
+}
+
+struct Bundler {
+    root: Url,
+    documents: HashMap<Url, JsonValue>,
+    /// Why each referenced document that failed to load did so (lenient mode only)
+    unloaded: HashMap<Url, String>,
+    /// Whether unresolved references are reported in `diagnostics` rather than failing
+    lenient: bool,
+    diagnostics: Vec<Diagnostic>,
+    /// Local reference assigned to each external target, keyed by its absolute URL
+    hoisted: HashMap<String, String>,
+    /// Component names already in use, as `<kind>/<name>`
+    taken: HashSet<String>,
+    /// Hoisted values waiting to be inserted into the root document
+    components: Vec<(String, String, JsonValue)>,
+}
+
+impl Bundler {
+    async fn load(location: &str, lenient: bool) -> Result<Self> {
+        let root = location_url(location)?;
+        let (documents, unloaded) = load_documents(&root, lenient).await?;
+        Ok(Self {
+            root,
+            documents,
+            unloaded,
+            lenient,
+            diagnostics: Vec::new(),
+            hoisted: HashMap::new(),
+            taken: HashSet::new(),
+            components: Vec::new(),
+        })
+    }
+
+    fn bundle_root(&mut self) -> Result<JsonValue> {
+        let mut root = self.documents[&self.root].clone();
+        if let Some(components) = root.get("components").and_then(JsonValue::as_object) {
+            for (kind, entries) in components {
+                for name in entries.as_object().into_iter().flat_map(JsonMap::keys) {
+                    self.taken.insert(format!("{kind}/{name}"));
+                }
+            }
+        }
+
+        let base = self.root.clone();
+        self.rewrite(&mut root, &base, &mut Vec::new())?;
+
+        if !self.components.is_empty() {
+            let components = root
+                .as_object_mut()
+                .ok_or_else(|| Error::openapi("OpenAPI document must be an object"))?
+                .entry("components")
+                .or_insert_with(|| JsonValue::Object(JsonMap::new()));
+            for (kind, name, value) in self.components.drain(..) {
+                if let Some(section) = components
+                    .as_object_mut()
+                    .map(|c| {
+                        c.entry(kind)
+                            .or_insert_with(|| JsonValue::Object(JsonMap::new()))
+                    })
+                    .and_then(JsonValue::as_object_mut)
+                {
+                    section.insert(name, value);
+                }
+            }
+        }
+        Ok(root)
+    }
+
+    /// Rewrite every `$ref` in `value`, which lives in the document at `base`
+    fn rewrite(&mut self, value: &mut JsonValue, base: &Url, path: &mut Vec<String>) -> Result<()> {
+        match value {
+            JsonValue::Object(map) => {
+                if let Some(ref_str) = map.get("$ref").and_then(JsonValue::as_str) {
+                    let ref_str = ref_str.to_string();
+                    if let Some(inlined) = self.resolve(&ref_str, map, base, path)? {
+                        *value = inlined;
+                        return Ok(());
+                    }
+                }
+                for (key, child) in map.iter_mut() {
+                    path.push(key.clone());
+                    self.rewrite(child, base, path)?;
+                    path.pop();
+                }
+            }
+            JsonValue::Array(items) => {
+                for (i, child) in items.iter_mut().enumerate() {
+                    path.push(i.to_string());
+                    self.rewrite(child, base, path)?;
+                    path.pop();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Point the `$ref` in `map` at a local target.
+    ///
+    /// Returns a replacement value when the target has to be inlined instead.
+    fn resolve(
+        &mut self,
+        ref_str: &str,
+        map: &mut JsonMap<String, JsonValue>,
+        base: &Url,
+        path: &mut Vec<String>,
+    ) -> Result<Option<JsonValue>> {
+        let (target, doc_url, pointer, resolved) = match self.lookup(ref_str, base) {
+            Ok(found) => found,
+            Err(reason) if self.lenient => {
+                let location = path.iter().fold(String::new(), |p, s| pointer_join(&p, s));
+                let origin = if *base == self.root {
+                    String::new()
+                } else {
+                    format!(" in {base}")
+                };
+                self.diagnostics.push(Diagnostic::error(
+                    pointer_join(&location, "$ref"),
+                    format!("Unresolved $ref '{ref_str}'{origin}: {reason}"),
+                ));
+                return Ok(None);
+            }
+            Err(reason) => return Err(unresolved(ref_str, base, path, &reason)),
+        };
+
+        // References into the root document stay local
+        if doc_url == self.root {
+            map.insert("$ref".to_string(), JsonValue::String(format!("#{pointer}")));
+            return Ok(None);
+        }
+
+        if let Some(local) = self.hoisted.get(target.as_str()) {
+            map.insert("$ref".to_string(), JsonValue::String(local.clone()));
+            return Ok(None);
+        }
+
+        // A root component that is only a reference (`Pet: {$ref: ./pet.yaml}`) takes the content itself
+        if *base == self.root
+            && let [components, kind, name] = path.as_slice()
+            && components == "components"
+        {
+            let local = format!("#/components/{kind}/{}", escape_segment(name));
+            self.hoisted.insert(target.to_string(), local);
+            let mut inlined = resolved;
+            self.rewrite(&mut inlined, &doc_url, path)?;
+            return Ok(Some(inlined));
+        }
+
+        match placement(&pointer, path, &doc_url) {
+            Placement::Inline => {
+                let mut inlined = resolved;
+                self.rewrite(&mut inlined, &doc_url, path)?;
+                Ok(Some(inlined))
+            }
+            Placement::Component { kind, name } => {
+                let name = self.unique_name(&kind, &name);
+                let local = format!("#/components/{kind}/{}", escape_segment(&name));
+                // Register before descending so cyclic references find the local copy
+                self.hoisted.insert(target.to_string(), local.clone());
+                map.insert("$ref".to_string(), JsonValue::String(local));
+
+                let mut hoisted = resolved;
+                let mut component_path = vec!["components".to_string(), kind.clone(), name.clone()];
+                self.rewrite(&mut hoisted, &doc_url, &mut component_path)?;
+                self.components.push((kind, name, hoisted));
+                Ok(None)
+            }
+        }
+    }
+
+    /// The URL, document URL, pointer and value `ref_str` points at, or why it
+    /// cannot be resolved
+    fn lookup(
+        &self,
+        ref_str: &str,
+        base: &Url,
+    ) -> std::result::Result<(Url, Url, String, JsonValue), String> {
+        let target = base
+            .join(ref_str)
+            .map_err(|e| format!("invalid reference ({e})"))?;
+        let doc_url = document_url(&target);
+        let pointer = fragment_to_pointer(target.fragment().unwrap_or(""));
+
+        let Some(document) = self.documents.get(&doc_url) else {
+            let reason = self.unloaded.get(&doc_url).map(String::as_str);
+            return Err(reason.unwrap_or("document was not loaded").to_string());
+        };
+        let resolved = document
+            .pointer(&pointer)
+            .cloned()
+            .ok_or_else(|| "target does not exist".to_string())?;
+        Ok((target, doc_url, pointer, resolved))
+    }
+
+    /// Pick a component name not used yet within `kind`
+    fn unique_name(&mut self, kind: &str, name: &str) -> String {
+        let mut candidate = name.to_string();
+        let mut n = 2;
+        while self.taken.contains(&format!("{kind}/{candidate}")) {
+            candidate = format!("{name}_{n}");
+            n += 1;
+        }
+        self.taken.insert(format!("{kind}/{candidate}"));
+        candidate
+    }
+}
+
+/// Decide where an external target goes, based on its own pointer and on
+/// where the reference appears
+fn placement(pointer: &str, path: &[String], doc_url: &Url) -> Placement {
+    let segments: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let name = segments
+        .last()
+        .cloned()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| {
+            Path::new(doc_url.path())
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "Document".to_string())
+        });
+    let name = sanitize_component_name(&name);
+
+    // The target declares its own kind: `#/components/<kind>/<name>` or Swagger's `#/definitions/<name>`
+    match segments.as_slice() {
+        [components, kind, _] if components == "components" => {
+            return Placement::Component {
+                kind: kind.clone(),
+                name,
+            };
+        }
+        [definitions, _] if definitions == "definitions" => {
+            return Placement::Component {
+                kind: "schemas".to_string(),
+                name,
+            };
+        }
+        _ => {}
+    }
+
+    // Otherwise infer the kind from the position of the reference
+    let kind = if path.iter().any(|s| SCHEMA_KEYS.contains(&s.as_str())) {
+        "schemas"
+    } else if let [.., parent, _] = path
+        && parent == "parameters"
+    {
+        "parameters"
+    } else if path.last().is_some_and(|s| s == "requestBody")
+        || matches!(path, [c, k, _] if c == "components" && k == "requestBodies")
+    {
+        "requestBodies"
+    } else if let [.., parent, _] = path
+        && let Some(kind) = COMPONENT_MAPS.iter().find(|key| *key == parent)
+    {
+        kind
+    } else if matches!(path, [paths, _] if paths == "paths") {
+        return Placement::Inline;
+    } else {
+        "schemas"
+    };
+
+    Placement::Component {
+        kind: kind.to_string(),
+        name,
+    }
+}
+
+/// Component names must match `^[a-zA-Z0-9.\-_]+$`
+fn sanitize_component_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn escape_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::diagnostics::Severity;
+    use serde_json::json;
+    use tempfile::tempdir;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let file = dir.join(name);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bundle_relative_files() {
+        let dir = tempdir().unwrap();
+        write(
+            dir.path(),
+            "openapi.yaml",
+            r##"
+openapi: 3.0.3
+info: {title: Pets, version: "1"}
+paths:
+  /pets/{petId}:
+    get:
+      operationId: getPet
+      parameters:
+        - $ref: './common.yaml#/components/parameters/PetId'
+      responses:
+        '200':
+          description: ok
+          content:
+            application/json:
+              schema: {$ref: './schemas/pet.yaml#/Pet'}
+        '404': {$ref: './common.yaml#/components/responses/NotFound'}
+components:
+  schemas:
+    Owner: {$ref: './schemas/owner.yaml'}
+"##,
+        );
+        write(
+            dir.path(),
+            "schemas/pet.yaml",
+            r##"
+Pet:
+  type: object
+  properties:
+    owner: {$ref: './owner.yaml'}
+    parent: {$ref: '#/Pet'}
+    tags: {type: array, items: {$ref: '#/Tag'}}
+Tag: {type: string}
+"##,
+        );
+        write(
+            dir.path(),
+            "schemas/owner.yaml",
+            "{type: object, properties: {name: {type: string}}}",
+        );
+        write(
+            dir.path(),
+            "common.yaml",
+            r##"
+components:
+  parameters:
+    PetId: {name: petId, in: path, required: true, schema: {type: string}}
+  responses:
+    NotFound:
+      description: missing
+      content: {application/json: {schema: {$ref: '#/components/schemas/Error'}}}
+  schemas:
+    Error: {type: object}
+"##,
+        );
+
+        let root = dir.path().join("openapi.yaml");
+        let doc = bundle(root.to_str().unwrap()).await.unwrap();
+        let get = &doc["paths"]["/pets/{petId}"]["get"];
+        let components = &doc["components"];
+
+        assert_eq!(
+            get["parameters"][0]["$ref"],
+            "#/components/parameters/PetId"
+        );
+        assert_eq!(components["parameters"]["PetId"]["name"], "petId");
+
+        let ok = &get["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(ok["$ref"], "#/components/schemas/Pet");
+        let pet = &components["schemas"]["Pet"]["properties"];
+        assert_eq!(pet["owner"]["$ref"], "#/components/schemas/Owner");
+        assert_eq!(pet["parent"]["$ref"], "#/components/schemas/Pet");
+        assert_eq!(pet["tags"]["items"]["$ref"], "#/components/schemas/Tag");
+        assert_eq!(
+            components["schemas"]["Owner"]["properties"]["name"]["type"],
+            "string"
+        );
+
+        assert_eq!(
+            get["responses"]["404"]["$ref"],
+            "#/components/responses/NotFound"
+        );
+        assert_eq!(
+            components["responses"]["NotFound"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Error"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bundle_escaped_pointers_and_name_clashes() {
+        let dir = tempdir().unwrap();
+        write(
+            dir.path(),
+            "openapi.json",
+            &json!({
+                "openapi": "3.0.3",
+                "paths": {},
+                "components": {"schemas": {
+                    "a/b": {"type": "string"},
+                    "Kind": {"type": "integer"},
+                    "Local": {"$ref": "#/components/schemas/a~1b"},
+                    "Remote": {"$ref": "defs.json#/defs/Pet%20Kind~1x"},
+                    "Other": {"$ref": "defs.json#/defs/Kind"}
+                }}
+            })
+            .to_string(),
+        );
+        write(
+            dir.path(),
+            "defs.json",
+            &json!({"defs": {"Pet Kind/x": {"type": "boolean"}, "Kind": {"type": "number"}}})
+                .to_string(),
+        );
+
+        let root = dir.path().join("openapi.json");
+        let doc = bundle(root.to_str().unwrap()).await.unwrap();
+        let schemas = &doc["components"]["schemas"];
+        assert_eq!(schemas["Local"]["$ref"], "#/components/schemas/a~1b");
+        assert_eq!(schemas["Remote"]["type"], "boolean");
+        // Root component entries that only point elsewhere take the content itself
+        assert_eq!(schemas["Other"]["type"], "number");
+        assert_eq!(schemas["Kind"]["type"], "integer");
+    }
+
+    #[tokio::test]
+    async fn test_bundle_reports_unresolved_refs() {
+        let dir = tempdir().unwrap();
+        write(
+            dir.path(),
+            "openapi.json",
+            &json!({"paths": {"/pets": {"get": {"responses": {"200": {
+                "$ref": "missing.yaml#/Ok"
+            }}}}}})
+            .to_string(),
+        );
+        let root = dir.path().join("openapi.json");
+        let err = bundle(root.to_str().unwrap())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'missing.yaml#/Ok'"), "{err}");
+        assert!(err.contains("openapi.json"), "{err}");
+        assert!(err.contains("#/paths/~1pets/get/responses/200"), "{err}");
+
+        write(
+            dir.path(),
+            "openapi.json",
+            &json!({"components": {"schemas": {"Pet": {"$ref": "#/components/schemas/Missing"}}}})
+                .to_string(),
+        );
+        let err = bundle(root.to_str().unwrap())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'#/components/schemas/Missing'"), "{err}");
+        assert!(err.contains("target does not exist"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_bundle_lenient_reports_unresolved_refs() {
+        let dir = tempdir().unwrap();
+        write(
+            dir.path(),
+            "pet.json",
+            &json!({"Pet": {"$ref": "#/Gone"}}).to_string(),
+        );
+        write(
+            dir.path(),
+            "openapi.json",
+            &json!({
+                "paths": {"/pets": {"get": {"responses": {
+                    "200": {"$ref": "missing.yaml#/Ok"},
+                    "default": {"$ref": "#/components/responses/Missing"}
+                }}}},
+                "components": {"schemas": {"Pet": {"$ref": "pet.json#/Pet"}}}
+            })
+            .to_string(),
+        );
+        let root = dir.path().join("openapi.json");
+        let (doc, diagnostics) = bundle_lenient(root.to_str().unwrap()).await.unwrap();
+
+        // Unresolved references are left as they are
+        let responses = &doc["paths"]["/pets"]["get"]["responses"];
+        assert_eq!(responses["200"]["$ref"], "missing.yaml#/Ok");
+        assert_eq!(
+            responses["default"]["$ref"],
+            "#/components/responses/Missing"
+        );
+        assert_eq!(doc["components"]["schemas"]["Pet"]["$ref"], "#/Gone");
+
+        let found: Vec<(&str, &str)> = diagnostics
+            .iter()
+            .map(|d| (d.pointer.as_str(), d.message.as_str()))
+            .collect();
+        assert_eq!(found.len(), 3, "{found:?}");
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(found[0].0, "/components/schemas/Pet/$ref");
+        assert!(found[0].1.contains("pet.json"), "{}", found[0].1);
+        assert_eq!(found[1].0, "/paths/~1pets/get/responses/200/$ref");
+        assert!(found[1].1.contains("Failed to read"), "{}", found[1].1);
+        assert_eq!(found[2].0, "/paths/~1pets/get/responses/default/$ref");
+        assert!(
+            found[2].1.ends_with(": target does not exist"),
+            "{}",
+            found[2].1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bundle_remote_relative_refs() {
+        let server = MockServer::start().await;
+        Mock::given(path("/specs/openapi.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "openapi": "3.0.3",
+                "paths": {"/pets": {"post": {"requestBody": {"content": {"application/json": {
+                    "schema": {"$ref": "models/pet.json#/Pet"}
+                }}}, "responses": {}}}}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/specs/models/pet.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Pet": {"type": "object", "properties": {"name": {"type": "string"}}}
+            })))
+            .mount(&server)
+            .await;
+
+        let doc = bundle(&format!("{}/specs/openapi.json", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(
+            doc["components"]["schemas"]["Pet"]["properties"]["name"]["type"],
+            "string"
+        );
+        let body = &doc["paths"]["/pets"]["post"]["requestBody"];
+        assert_eq!(
+            body["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+    }
+}
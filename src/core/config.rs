@@ -22,10 +22,13 @@
 //! ```
 
 // Internal imports (std, crate)
+use std::collections::BTreeMap;
 use std::path::Path;
 
 // External imports (alphabetized)
 use crate::core::error::Result;
+use crate::core::openapi::HttpMethod;
+use crate::core::tool_names::ToolNaming;
 use serde::{Deserialize, Serialize};
 use tokio::fs;
 use url::Url;
@@ -50,20 +53,60 @@ pub struct Config {
     #[serde(default)]
     pub template_dir: Option<String>,
 
-    /// Whether to include all operations by default
+    /// Whether to include all operations, ignoring the include filters
     #[serde(default)]
     pub include_all: bool,
 
-    /// List of operations to include (if include_all is false)
+    /// Operation ids or tool names to include (if include_all is false)
     #[serde(default)]
     pub include_operations: Vec<String>,
 
-    /// List of operations to exclude
+    /// Operation ids or tool names to exclude
     #[serde(default)]
     pub exclude_operations: Vec<String>,
 
+    /// Include only operations with one of these tags (if include_all is false)
+    #[serde(default)]
+    pub include_tags: Vec<String>,
+
+    /// Exclude operations with any of these tags
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+
+    /// Include only operations whose path matches one of these globs (if include_all is false)
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+
+    /// Exclude operations whose path matches any of these globs
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+
+    /// Include only operations with one of these HTTP methods (if include_all is false)
+    #[serde(default)]
+    pub include_methods: Vec<HttpMethod>,
+
+    /// Exclude operations with any of these HTTP methods
+    #[serde(default)]
+    pub exclude_methods: Vec<HttpMethod>,
+
     /// Base URL of the OpenAPI specification (Optional)
     pub base_url: Option<Url>,
+
+    /// Index of the spec server to generate against (defaults to the first)
+    #[serde(default)]
+    pub server_index: Option<usize>,
+
+    /// Use the first spec server whose URL contains this text
+    #[serde(default)]
+    pub server_url_match: Option<String>,
+
+    /// Values for server URL variables, overriding their defaults
+    #[serde(default)]
+    pub server_variables: BTreeMap<String, String>,
+
+    /// How tool names are derived from operations
+    #[serde(default)]
+    pub tool_naming: ToolNaming,
 }
 
 #[allow(dead_code)]
@@ -83,7 +126,17 @@ impl Config {
             include_all: false,
             include_operations: Vec::new(),
             exclude_operations: Vec::new(),
+            include_tags: Vec::new(),
+            exclude_tags: Vec::new(),
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            include_methods: Vec::new(),
+            exclude_methods: Vec::new(),
             base_url: None,
+            server_index: None,
+            server_url_match: None,
+            server_variables: BTreeMap::new(),
+            tool_naming: ToolNaming::default(),
         }
     }
 
@@ -128,6 +181,7 @@ mod tests {
         assert_eq!(config.include_operations, Vec::<String>::new());
         assert_eq!(config.exclude_operations, Vec::<String>::new());
         assert_eq!(config.base_url, None);
+        assert!(config.server_variables.is_empty());
 
         Ok(())
     }
@@ -0,0 +1,96 @@
+//! Problems found while reading an OpenAPI specification.
+//!
+//! Parsing is lenient: a malformed parameter or response does not abort
+//! generation. Instead it is left out of the parsed operation and reported as
+//! a [`Diagnostic`] pointing at the offending part of the document, so users
+//! can see exactly what was skipped and why.
+
+// External imports (alphabetized)
+use serde::Serialize;
+use std::fmt;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Something is unusual but was understood
+    Warning,
+    /// Part of the document is invalid and was left out
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem at a specific location of an OpenAPI document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// How serious the problem is
+    pub severity: Severity,
+    /// JSON pointer to the offending value (e.g. `/paths/~1pets/get/parameters/0`)
+    pub pointer: String,
+    /// Human readable explanation
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic at `pointer`
+    pub fn error(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a warning diagnostic at `pointer`
+    pub fn warning(pointer: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            pointer: pointer.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at #{}: {}",
+            self.severity, self.pointer, self.message
+        )
+    }
+}
+
+/// Append a JSON pointer segment, escaping `~` and `/` as RFC 6901 requires
+pub fn pointer_join(pointer: &str, segment: &str) -> String {
+    format!(
+        "{pointer}/{}",
+        segment.replace('~', "~0").replace('/', "~1")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_display_and_pointer_escaping() {
+        let pointer = pointer_join(&pointer_join("/paths", "/pets/{id}"), "get");
+        assert_eq!(pointer, "/paths/~1pets~1{id}/get");
+
+        let diagnostic = Diagnostic::error(pointer, "missing field `in`");
+        assert_eq!(
+            diagnostic.to_string(),
+            "error at #/paths/~1pets~1{id}/get: missing field `in`"
+        );
+        assert!(Severity::Error > Severity::Warning);
+    }
+}
@@ -0,0 +1,308 @@
+//! Events the API sends on its own: OpenAPI 3.1 `webhooks` and the
+//! `callbacks` of operations.
+//!
+//! Each method of a webhook or callback path item becomes one [`ApiEvent`].
+//! Generated servers can receive these requests over HTTP and forward them to
+//! MCP clients, so every event gets a unique snake_case `name` that is used in
+//! its receiver path (`/events/{name}`) and its resource URI
+//! (`events://{name}`). Webhooks are named after their key, callbacks after the
+//! declaring tool and the callback key, e.g. `create_payment_on_settled`.
+
+// Internal imports (std, crate)
+use std::collections::BTreeSet;
+
+use crate::core::diagnostics::{Diagnostic, pointer_join};
+use crate::core::openapi::{HttpMethod, OpenApiContext, OpenApiOperation};
+use crate::core::utils::to_snake_case;
+
+// External imports (alphabetized)
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Where an event is declared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSource {
+    /// Under the document's `webhooks`
+    Webhook,
+    /// Under an operation's `callbacks`
+    Callback,
+}
+
+/// A request the API sends to a receiver, rather than one it receives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiEvent {
+    /// Unique snake_case name
+    pub name: String,
+    pub source: EventSource,
+    /// Webhook or callback name, as written in the spec
+    pub key: String,
+    /// Tool name of the operation declaring a callback
+    pub operation: Option<String>,
+    /// Runtime expression of a callback URL, e.g. `{$request.body#/callbackUrl}`
+    pub expression: Option<String>,
+    /// HTTP method the API sends the event with
+    pub method: HttpMethod,
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    /// Schema of the JSON payload, with its references inlined
+    pub payload_schema: Option<JsonValue>,
+    /// JSON pointer to the event's operation in the spec
+    pub pointer: String,
+}
+
+/// Collect the webhooks of `spec` and the callbacks of `operations`, in
+/// document order, reporting path items that are not objects.
+pub fn collect_events(
+    spec: &OpenApiContext,
+    operations: &[OpenApiOperation],
+) -> (Vec<ApiEvent>, Vec<Diagnostic>) {
+    let mut events = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    if let Some(webhooks) = spec.json.get("webhooks").and_then(JsonValue::as_object) {
+        for (key, item) in webhooks {
+            let pointer = pointer_join("/webhooks", key);
+            let declared = Declaration {
+                source: EventSource::Webhook,
+                key,
+                operation: None,
+                expression: None,
+                base_name: name_part(key),
+            };
+            push_events(
+                spec,
+                &declared,
+                item,
+                &pointer,
+                &mut events,
+                &mut diagnostics,
+            );
+        }
+    }
+
+    for op in operations {
+        let Some(callbacks) = op.callbacks.as_ref().and_then(JsonValue::as_object) else {
+            continue;
+        };
+        let callbacks_pointer = pointer_join(&op.pointer(), "callbacks");
+        for (key, callback) in callbacks {
+            let callback_pointer = pointer_join(&callbacks_pointer, key);
+            let callback = resolve(spec, callback);
+            let Some(expressions) = callback.as_object() else {
+                diagnostics.push(Diagnostic::error(
+                    callback_pointer,
+                    "Callback is not an object; skipped",
+                ));
+                continue;
+            };
+            for (expression, item) in expressions {
+                let declared = Declaration {
+                    source: EventSource::Callback,
+                    key,
+                    operation: Some(&op.tool_name),
+                    expression: Some(expression),
+                    base_name: format!("{}_{}", op.tool_name, name_part(key)),
+                };
+                let pointer = pointer_join(&callback_pointer, expression);
+                push_events(
+                    spec,
+                    &declared,
+                    item,
+                    &pointer,
+                    &mut events,
+                    &mut diagnostics,
+                );
+            }
+        }
+    }
+
+    dedupe_names(&mut events);
+    (events, diagnostics)
+}
+
+/// What declares a path item of events
+struct Declaration<'a> {
+    source: EventSource,
+    key: &'a str,
+    operation: Option<&'a str>,
+    expression: Option<&'a str>,
+    base_name: String,
+}
+
+/// Add one event per method of the path `item`
+fn push_events(
+    spec: &OpenApiContext,
+    declared: &Declaration,
+    item: &JsonValue,
+    pointer: &str,
+    events: &mut Vec<ApiEvent>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(item) = resolve(spec, item).as_object() else {
+        diagnostics.push(Diagnostic::error(
+            pointer,
+            "Path item is not an object; skipped",
+        ));
+        return;
+    };
+    let methods: Vec<&HttpMethod> = HttpMethod::all()
+        .iter()
+        .filter(|method| item.get(method.as_str()).is_some_and(JsonValue::is_object))
+        .collect();
+    for &method in &methods {
+        let operation = &item[method.as_str()];
+        let text = |field: &str| {
+            operation
+                .get(field)
+                .and_then(JsonValue::as_str)
+                .map(String::from)
+        };
+        // Only several methods on one path item need telling apart
+        let name = if methods.len() > 1 {
+            format!("{}_{}", declared.base_name, method.as_str())
+        } else {
+            declared.base_name.clone()
+        };
+        events.push(ApiEvent {
+            name,
+            source: declared.source,
+            key: declared.key.to_string(),
+            operation: declared.operation.map(String::from),
+            expression: declared.expression.map(String::from),
+            method: method.clone(),
+            summary: text("summary"),
+            description: text("description"),
+            payload_schema: payload_schema(spec, operation),
+            pointer: pointer_join(pointer, method.as_str()),
+        });
+    }
+}
+
+/// Follow a `$ref` to a path item or callback, leaving anything else as is
+fn resolve<'a>(spec: &'a OpenApiContext, value: &'a JsonValue) -> &'a JsonValue {
+    value
+        .get("$ref")
+        .and_then(JsonValue::as_str)
+        .and_then(|ref_str| spec.resolve_ref(ref_str))
+        .unwrap_or(value)
+}
+
+/// Schema of the JSON request body of an event's operation
+fn payload_schema(spec: &OpenApiContext, operation: &JsonValue) -> Option<JsonValue> {
+    let body = resolve(spec, operation.get("requestBody")?);
+    let content = body.get("content")?.as_object()?;
+    let media = content.get("application/json").or_else(|| {
+        content
+            .iter()
+            .filter(|(media_type, _)| media_type.ends_with("+json"))
+            .min_by_key(|(media_type, _)| *media_type)
+            .map(|(_, media)| media)
+    })?;
+    let (mut schema, defs) = spec.inline_refs(media.get("schema")?);
+    if !defs.is_empty()
+        && let Some(object) = schema.as_object_mut()
+    {
+        object.insert("$defs".to_string(), JsonValue::Object(defs));
+    }
+    Some(schema)
+}
+
+/// snake_case of `text` with only ASCII letters and digits, as names end up in
+/// URLs
+fn name_part(text: &str) -> String {
+    to_snake_case(&text.replace(|c: char| !c.is_ascii_alphanumeric(), "_"))
+}
+
+/// Number clashing names in order (`settled`, `settled_2`, ...), keeping
+/// `log` free
+fn dedupe_names(events: &mut [ApiEvent]) {
+    // `events://log` is the resource listing every event
+    let mut taken = BTreeSet::from(["log".to_string()]);
+    for event in events.iter_mut() {
+        let base = if event.name.is_empty() {
+            "event".to_string()
+        } else {
+            event.name.clone()
+        };
+        let mut name = base.clone();
+        let mut counter = 2;
+        while taken.contains(&name) {
+            name = format!("{base}_{counter}");
+            counter += 1;
+        }
+        taken.insert(name.clone());
+        event.name = name;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_collect_webhooks_and_callbacks() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.1.0",
+                "paths": {"/payments": {"post": {
+                    "operationId": "createPayment",
+                    "callbacks": {"onSettled": {
+                        "{$request.body#/callbackUrl}": {"post": {
+                            "summary": "Payment settled",
+                            "requestBody": {"content": {"application/json": {
+                                "schema": {"$ref": "#/components/schemas/Payment"}
+                            }}},
+                            "responses": {"200": {"description": "ok"}}
+                        }}
+                    }},
+                    "responses": {"201": {"description": "created"}}
+                }}},
+                "webhooks": {
+                    "build-finished": {"$ref": "#/components/pathItems/Build"},
+                    "broken": "nope"
+                },
+                "components": {
+                    "schemas": {"Payment": {"type": "object", "properties": {"id": {"type": "string"}}}},
+                    "pathItems": {"Build": {
+                        "post": {"description": "A build finished", "responses": {}},
+                        "put": {"responses": {}}
+                    }}
+                }
+            }),
+        };
+        let mut operations = spec.parse_operations().await.unwrap();
+        operations[0].tool_name = "create_payment".to_string();
+
+        let (events, diagnostics) = collect_events(&spec, &operations);
+        let names: Vec<&str> = events.iter().map(|event| event.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "build_finished_post",
+                "build_finished_put",
+                "create_payment_on_settled"
+            ]
+        );
+        assert_eq!(events[0].source, EventSource::Webhook);
+        assert_eq!(events[0].description.as_deref(), Some("A build finished"));
+        assert_eq!(events[0].pointer, "/webhooks/build-finished/post");
+
+        let callback = &events[2];
+        assert_eq!(callback.source, EventSource::Callback);
+        assert_eq!(callback.operation.as_deref(), Some("create_payment"));
+        assert_eq!(
+            callback.expression.as_deref(),
+            Some("{$request.body#/callbackUrl}")
+        );
+        assert_eq!(callback.method, HttpMethod::Post);
+        assert_eq!(
+            callback.payload_schema.as_ref().unwrap()["properties"]["id"]["type"],
+            "string"
+        );
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pointer, "/webhooks/broken");
+    }
+}
@@ -0,0 +1,155 @@
+//! The `x-mcp` specification extension, which lets API owners curate the
+//! generated MCP tools from within their spec.
+//!
+//! On an operation:
+//!
+//! ```yaml
+//! x-mcp:
+//!   name: search_pets           # tool name, instead of the naming strategy's
+//!   description: Find pets ...  # tool description, instead of summary and description
+//!   hidden: true                # generate no tool for this operation
+//!   resource: false             # do not expose this GET operation as a resource
+//!   annotations:                # hints for MCP clients
+//!     title: Search pets
+//!     readOnly: true
+//!     destructive: false
+//!     idempotent: true
+//!     openWorld: true
+//! ```
+//!
+//! On a parameter:
+//!
+//! ```yaml
+//! x-mcp:
+//!   hidden: true     # not a tool argument
+//!   default: 20      # sent when no value is given; always sent when hidden
+//!   config: api_key  # not a tool argument; sent from the server config's `params.api_key`
+//! ```
+//!
+//! Unknown keys are rejected, so that typos do not go unnoticed.
+
+// External imports (alphabetized)
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+/// Name of the extension
+pub const MCP_EXTENSION: &str = "x-mcp";
+
+/// `x-mcp` extension of an operation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct McpOperationExtension {
+    /// Tool name, still subject to the tool-name rules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Tool description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Leave the operation out of the generated server
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Whether a GET operation is exposed as an MCP resource, which it is by
+    /// default when its only required arguments are path parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<bool>,
+    /// MCP tool annotations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<McpToolAnnotations>,
+}
+
+impl McpOperationExtension {
+    /// Whether the extension is absent or customizes nothing
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Hints about a tool's behavior, as defined by the MCP specification
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct McpToolAnnotations {
+    /// Human-readable title of the tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The tool does not modify its environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// The tool may delete or overwrite data, rather than only add to it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive: Option<bool>,
+    /// Repeating a call with the same arguments has no additional effect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent: Option<bool>,
+    /// The tool interacts with an open world of external entities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_world: Option<bool>,
+}
+
+/// `x-mcp` extension of a parameter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpParameterExtension {
+    /// Do not expose the parameter as a tool argument
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    /// Value sent when the tool call (or the server config) gives none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<JsonValue>,
+    /// Key under `params` in the server config that supplies the value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+}
+
+impl McpParameterExtension {
+    /// Whether the extension is absent or customizes nothing
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the parameter is set by the server rather than by the caller
+    pub fn is_injected(&self) -> bool {
+        self.hidden || self.config.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_mcp_extensions() {
+        let operation: McpOperationExtension = serde_json::from_value(json!({
+            "name": "search_pets",
+            "annotations": {"readOnly": true, "openWorld": false}
+        }))
+        .unwrap();
+        assert_eq!(operation.name.as_deref(), Some("search_pets"));
+        assert!(!operation.hidden);
+        assert_eq!(operation.resource, None);
+        let annotations = operation.annotations.unwrap();
+        assert_eq!(annotations.read_only, Some(true));
+        assert_eq!(annotations.open_world, Some(false));
+        assert_eq!(annotations.destructive, None);
+
+        let parameter: McpParameterExtension =
+            serde_json::from_value(json!({"config": "tenant", "default": "acme"})).unwrap();
+        assert!(parameter.is_injected());
+        assert_eq!(parameter.default, Some(json!("acme")));
+        assert!(McpParameterExtension::default().is_empty());
+
+        let operation: McpOperationExtension =
+            serde_json::from_value(json!({"resource": false})).unwrap();
+        assert_eq!(operation.resource, Some(false));
+        assert!(!operation.is_empty());
+
+        // Typos are errors rather than silently ignored
+        assert!(serde_json::from_value::<McpOperationExtension>(json!({"hiden": true})).is_err());
+        assert!(
+            serde_json::from_value::<McpOperationExtension>(
+                json!({"annotations": {"readonly": true}})
+            )
+            .is_err()
+        );
+    }
+}
@@ -3,11 +3,20 @@
 //! This library provides the core functionality for generating AI agent
 //! server code from OpenAPI specifications.
 
+pub mod auth;
+pub mod bundler;
 pub mod config;
+pub mod diagnostics;
 pub mod error;
+pub mod events;
+pub mod extensions;
 pub mod openapi;
 pub mod protocol;
+pub mod selection;
+pub mod swagger;
 pub mod templates;
+pub mod tool_names;
 pub mod utils;
+pub mod validate;
 
 pub use error::Error;
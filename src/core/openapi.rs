@@ -1,7 +1,7 @@
 //! OpenAPI specification parsing and utilities.
 //!
 //! This module provides functionality for loading and querying OpenAPI specifications.
-//! It supports loading from files and provides convenient accessors for common fields.
+//! It supports loading from files and URLs and provides convenient accessors for common fields.
 //!
 //! # Examples
 //!
@@ -12,7 +12,7 @@
 //! # #[tokio::main]
 //! # async fn main() -> Result<()> {
 //! // Load an OpenAPI spec from a file
-//! let spec = OpenApiContext::from_file("openapi.json").await?;
+//! let spec = OpenApiContext::from_file_or_url("openapi.json").await?;
 //!
 //! // Access common fields
 //! if let Some(title) = spec.title() {
@@ -26,16 +26,22 @@
 //! ```
 
 // Internal imports (std, crate)
-use std::path::Path;
+use std::collections::BTreeMap;
 
 use crate::core::Error;
+use crate::core::bundler::fragment_to_pointer;
+use crate::core::diagnostics::{Diagnostic, pointer_join};
+use crate::core::extensions::{MCP_EXTENSION, McpOperationExtension, McpParameterExtension};
+use crate::core::swagger;
 
 // External imports (alphabetized)
+use openapiv3::{SecurityRequirement, SecurityScheme, Server};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_json::{Value as JsonValue, json};
+use serde_json::{Map as JsonMap, Value as JsonValue, json};
 use std::fmt;
 use std::str::FromStr;
-use tokio::fs;
+use tracing::warn;
 
 /// HTTP methods supported by OpenAPI
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -110,65 +116,41 @@ pub struct OpenApiContext {
 }
 
 impl OpenApiContext {
-    /// Create a new OpenAPISpec from a file or URL (supports both YAML and JSON)
+    /// Load a spec from a file or URL (YAML or JSON) and bundle every document
+    /// it references.
+    ///
+    /// External `$ref`s (relative files, remote URLs) are copied into the
+    /// document's `components` and rewritten as local references; any reference
+    /// that cannot be resolved is reported as an error.
     pub async fn from_file_or_url<P: AsRef<str>>(location: P) -> crate::core::error::Result<Self> {
-        let location = location.as_ref();
-
-        // Check if the input looks like a URL
-        if location.starts_with("http://") || location.starts_with("https://") {
-            return Self::from_url(location).await;
-        }
-
-        // Otherwise treat as a file path
-        Self::from_file(location).await
-    }
-
-    /// Create a new OpenAPISpec from a file (supports both YAML and JSON)
-    pub async fn from_file<P: AsRef<Path>>(path: P) -> crate::core::error::Result<Self> {
-        let path = path.as_ref();
-        let content = fs::read_to_string(path).await?;
-        Self::parse_content(&content).map_err(|e| {
-            crate::core::Error::openapi(format!(
-                "Failed to parse OpenAPI spec at {}: {}",
-                path.display(),
-                e
-            ))
-        })
+        let json = crate::core::bundler::bundle(location.as_ref()).await?;
+        Ok(Self { json })
     }
 
-    /// Create a new OpenAPISpec from a URL (supports both YAML and JSON)
-    async fn from_url(url: &str) -> crate::core::error::Result<Self> {
-        let response = reqwest::get(url).await.map_err(|e| {
-            crate::core::Error::openapi(format!("Failed to fetch OpenAPI spec from {url}: {e}"))
-        })?;
-
-        if !response.status().is_success() {
-            return Err(crate::core::Error::openapi(format!(
-                "Failed to fetch OpenAPI spec from {}: HTTP {}",
-                url,
-                response.status()
-            )));
-        }
-
-        let content = response.text().await.map_err(|e| {
-            crate::core::Error::openapi(format!("Failed to read response from {url}: {e}"))
-        })?;
-
-        Self::parse_content(&content).map_err(|e| {
-            crate::core::Error::openapi(format!("Failed to parse OpenAPI spec from {url}: {e}"))
-        })
+    /// Load a spec like [`Self::from_file_or_url`], but leave references that
+    /// cannot be resolved in place and return them as error diagnostics, so
+    /// that the rest of the spec can still be validated.
+    pub async fn from_file_or_url_with_diagnostics<P: AsRef<str>>(
+        location: P,
+    ) -> crate::core::error::Result<(Self, Vec<Diagnostic>)> {
+        let (json, diagnostics) = crate::core::bundler::bundle_lenient(location.as_ref()).await?;
+        Ok((Self { json }, diagnostics))
     }
 
-    /// Parse content as either JSON or YAML
-    fn parse_content(content: &str) -> Result<Self, String> {
+    /// Parse content as either JSON or YAML, upgrading Swagger 2.0 documents to OpenAPI 3.0
+    pub(crate) fn parse_content(content: &str) -> Result<Self, String> {
         // Try to parse as JSON first
         if let Ok(json) = serde_json::from_str(content) {
-            return Ok(Self { json });
+            return Ok(Self {
+                json: swagger::upgrade(json),
+            });
         }
 
         // If JSON parsing fails, try YAML
         if let Ok(json) = serde_yaml::from_str(content) {
-            return Ok(Self { json });
+            return Ok(Self {
+                json: swagger::upgrade(json),
+            });
         }
 
         // If both parsers fail, return an error
@@ -184,15 +166,26 @@ impl OpenApiContext {
         self.json.get("info")?.get("version")?.as_str()
     }
 
-    /// Get the base path of the API
+    /// Whether the document declares OpenAPI 3.1 (JSON Schema 2020-12 schemas)
+    pub fn is_v3_1(&self) -> bool {
+        self.json
+            .get("openapi")
+            .and_then(JsonValue::as_str)
+            .is_some_and(|v| v.starts_with("3.1"))
+    }
+
+    /// Get the base path of the API.
+    ///
+    /// This is the first server's URL with its variables set to their defaults.
     pub fn base_path(&self) -> Option<String> {
         // Try OpenAPI 3.0+ servers format first
-        if let Some(servers) = self.json.get("servers").and_then(|s| s.as_array()) {
-            if let Some(server) = servers.first() {
-                if let Some(url) = server.get("url").and_then(|u| u.as_str()) {
-                    return Some(url.to_string());
-                }
-            }
+        if let Some(server) = self.json.pointer("/servers/0")
+            && let Some(url) = server.get("url").and_then(|u| u.as_str())
+        {
+            let resolved = serde_json::from_value::<Server>(server.clone())
+                .ok()
+                .and_then(|server| resolve_server_url(&server, &BTreeMap::new()).ok());
+            return Some(resolved.unwrap_or_else(|| url.to_string()));
         }
 
         // Fall back to Swagger 2.0 host + basePath format
@@ -222,56 +215,170 @@ impl OpenApiContext {
         None
     }
 
-    /// Parse all endpoints into structured contexts for template rendering
+    /// Parse all endpoints into structured contexts for template rendering.
+    ///
+    /// Parts of the document that cannot be parsed are left out and logged;
+    /// use [`Self::parse_operations_with_diagnostics`] to inspect them instead.
     pub async fn parse_operations(&self) -> crate::core::error::Result<Vec<OpenApiOperation>> {
-        // Get paths object
-        let paths = self
-            .json
-            .get("paths")
-            .and_then(JsonValue::as_object)
-            .ok_or_else(|| Error::openapi("Missing 'paths' object"))?;
+        let (operations, diagnostics) = self.parse_operations_with_diagnostics().await?;
+        for diagnostic in &diagnostics {
+            warn!("{diagnostic}");
+        }
+        Ok(operations)
+    }
+
+    /// Parse all endpoints, returning the problems found along the way.
+    ///
+    /// A malformed parameter, request body or response is skipped and reported
+    /// as a [`Diagnostic`] pointing at it, rather than failing the whole parse.
+    pub async fn parse_operations_with_diagnostics(
+        &self,
+    ) -> crate::core::error::Result<(Vec<OpenApiOperation>, Vec<Diagnostic>)> {
+        // Get paths object (optional since OpenAPI 3.1, e.g. for webhook-only documents)
+        let Some(paths) = self.json.get("paths").and_then(JsonValue::as_object) else {
+            if self.is_v3_1() {
+                return Ok((Vec::new(), Vec::new()));
+            }
+            return Err(Error::openapi("Missing 'paths' object"));
+        };
+
+        let mut operations = Vec::new();
+        let mut diagnostics = Vec::new();
+        // Operations without a `security` of their own use the document's
+        let default_security: Option<Vec<SecurityRequirement>> =
+            self.json.get("security").and_then(|security| {
+                self.parse_object(
+                    security.clone(),
+                    "/security",
+                    "security requirement list",
+                    &mut diagnostics,
+                )
+            });
+        for (path, path_item) in paths {
+            let item_pointer = pointer_join("/paths", path);
+            let Some(path_item) = path_item.as_object() else {
+                diagnostics.push(Diagnostic::error(
+                    item_pointer,
+                    "Path item is not an object; skipped",
+                ));
+                continue;
+            };
+            for method in HttpMethod::all() {
+                let Some(method_item) = path_item.get(method.as_str()) else {
+                    continue;
+                };
+                let pointer = pointer_join(&item_pointer, method.as_str());
+                let Some(method_item) = method_item.as_object() else {
+                    diagnostics.push(Diagnostic::error(
+                        pointer,
+                        "Operation is not an object; skipped",
+                    ));
+                    continue;
+                };
+                let location = OperationLocation {
+                    path,
+                    method,
+                    path_item,
+                    item_pointer: &item_pointer,
+                    pointer: &pointer,
+                };
+                let mut operation = self.build_operation(&location, method_item, &mut diagnostics);
+                if operation.security.is_none() {
+                    operation.security = default_security.clone();
+                }
+                operations.push(operation);
+            }
+        }
 
-        // Use iterator combinators to flatten and map operations
-        let operations = paths
+        Ok((operations, diagnostics))
+    }
+
+    /// Servers declared under `servers`, in document order.
+    ///
+    /// Entries that do not match the OpenAPI model are left out and reported.
+    /// Documents without a `servers` array fall back to [`Self::base_path`].
+    pub fn servers(&self) -> (Vec<Server>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let Some(entries) = self.json.get("servers").and_then(JsonValue::as_array) else {
+            let servers = self
+                .base_path()
+                .map(|url| Server {
+                    url,
+                    ..Server::default()
+                })
+                .into_iter()
+                .collect();
+            return (servers, diagnostics);
+        };
+        let servers = entries
             .iter()
-            .flat_map(|(path, path_item)| {
-                HttpMethod::all()
-                    .iter()
-                    .filter_map(|method| {
-                        path_item
-                            .get(method.as_str())
-                            .and_then(JsonValue::as_object)
-                            .map(|method_item| (path, method, path_item, method_item))
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .map(|(path, method, path_item, method_item)| {
-                self.build_operation(path, method, path_item, method_item)
+            .enumerate()
+            .filter_map(|(index, server)| {
+                self.parse_object(
+                    server.clone(),
+                    &format!("/servers/{index}"),
+                    "server",
+                    &mut diagnostics,
+                )
             })
             .collect();
+        (servers, diagnostics)
+    }
 
-        Ok(operations)
+    /// Security schemes declared under `components/securitySchemes`, keyed by name.
+    ///
+    /// Schemes that do not match the OpenAPI model are left out and reported.
+    pub fn security_schemes(&self) -> (BTreeMap<String, SecurityScheme>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let schemes = self
+            .json
+            .pointer("/components/securitySchemes")
+            .and_then(JsonValue::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, scheme)| {
+                let pointer = pointer_join("/components/securitySchemes", name);
+                self.parse_object(
+                    self.resolve_shallow(scheme),
+                    &pointer,
+                    "security scheme",
+                    &mut diagnostics,
+                )
+                .map(|scheme| (name.clone(), scheme))
+            })
+            .collect();
+        (schemes, diagnostics)
     }
 
     /// Build an OpenApiOperation from path, method, and method item
     fn build_operation(
         &self,
-        path: &str,
-        method: &HttpMethod,
-        path_item: &JsonValue,
-        method_item: &serde_json::Map<String, JsonValue>,
+        location: &OperationLocation<'_>,
+        method_item: &JsonMap<String, JsonValue>,
+        diagnostics: &mut Vec<Diagnostic>,
     ) -> OpenApiOperation {
-        let operation_id = method_item
-            .get("operationId")
-            .and_then(JsonValue::as_str)
-            .map(String::from)
-            .unwrap_or_else(|| {
+        let OperationLocation {
+            path,
+            method,
+            pointer,
+            ..
+        } = *location;
+        let operation_id = match method_item.get("operationId") {
+            Some(JsonValue::String(id)) => id.clone(),
+            other => {
+                if other.is_some() {
+                    diagnostics.push(Diagnostic::warning(
+                        pointer_join(pointer, "operationId"),
+                        "operationId is not a string; a name is derived from the path instead",
+                    ));
+                }
                 format!(
                     "{}_{}",
                     method,
                     path.trim_start_matches('/').replace('/', "_")
                 )
-            });
+            }
+        };
 
         let summary = method_item
             .get("summary")
@@ -282,15 +389,26 @@ impl OpenApiContext {
             .and_then(JsonValue::as_str)
             .map(String::from);
         let external_docs = method_item.get("externalDocs").cloned();
-        let parameters = self.extract_parameters(path_item);
-        let request_body = method_item.get("requestBody").cloned();
-        let responses = self.extract_responses(method_item);
+        let parameters = self.extract_parameters(location, method_item, diagnostics);
+        let request_body = method_item.get("requestBody").and_then(|body| {
+            self.parse_object(
+                self.inline_object_refs(body),
+                &pointer_join(pointer, "requestBody"),
+                "request body",
+                diagnostics,
+            )
+        });
+        let responses = self.extract_responses(method_item, pointer, diagnostics);
         let callbacks = method_item.get("callbacks").cloned();
         let deprecated = method_item.get("deprecated").and_then(JsonValue::as_bool);
-        let security = method_item
-            .get("security")
-            .and_then(JsonValue::as_array)
-            .cloned();
+        let security = method_item.get("security").and_then(|security| {
+            self.parse_object(
+                security.clone(),
+                &pointer_join(pointer, "security"),
+                "security requirement list",
+                diagnostics,
+            )
+        });
         let servers = method_item
             .get("servers")
             .and_then(JsonValue::as_array)
@@ -304,10 +422,19 @@ impl OpenApiContext {
                     .map(String::from)
                     .collect()
             });
-        let vendor_extensions = self.extract_vendor_extensions(method_item);
+        let mcp: McpOperationExtension = parse_mcp_extension(method_item, pointer, diagnostics);
+        if mcp.resource == Some(true) && *method != HttpMethod::Get {
+            diagnostics.push(Diagnostic::warning(
+                pointer_join(&pointer_join(pointer, MCP_EXTENSION), "resource"),
+                "Only GET operations can be resources; ignored",
+            ));
+        }
+        let mut vendor_extensions = self.extract_vendor_extensions(method_item);
+        vendor_extensions.remove(MCP_EXTENSION);
 
         OpenApiOperation {
             id: operation_id,
+            tool_name: String::new(),
             path: path.to_string(),
             method: method.clone(),
             summary,
@@ -321,48 +448,252 @@ impl OpenApiContext {
             security,
             servers,
             tags,
+            mcp,
             vendor_extensions,
         }
     }
 
-    /// Extracts parameters from an OpenAPI path item, resolving any $ref references
-    fn extract_parameters(&self, path_item: &JsonValue) -> Option<Vec<OpenApiParameter>> {
-        path_item
-            .get("parameters")
-            .and_then(JsonValue::as_array)
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|param| {
-                        if let Some(ref_str) = param.get("$ref").and_then(JsonValue::as_str) {
-                            self.json
-                                .pointer(&ref_str[1..])
-                                .and_then(|p| serde_json::from_value(p.clone()).ok())
-                        } else {
-                            serde_json::from_value(param.clone()).ok()
-                        }
-                    })
-                    .collect::<Vec<OpenApiParameter>>()
+    /// Deserialize one OpenAPI object into its typed form.
+    ///
+    /// Returns `None` and records an error diagnostic at `pointer` when `value`
+    /// is still an unresolved `$ref` or does not match `T`. References deeper
+    /// inside an accepted object that do not resolve are reported as warnings.
+    fn parse_object<T: DeserializeOwned>(
+        &self,
+        value: JsonValue,
+        pointer: &str,
+        what: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Option<T> {
+        if let Some(ref_str) = value.get("$ref").and_then(JsonValue::as_str) {
+            diagnostics.push(Diagnostic::error(
+                pointer,
+                format!("Unresolved {what} $ref '{ref_str}'; skipped"),
+            ));
+            return None;
+        }
+        report_unresolved_refs(self, &value, pointer, diagnostics);
+        serde_json::from_value(value)
+            .map_err(|e| {
+                diagnostics.push(Diagnostic::error(
+                    pointer,
+                    format!("Invalid {what}; skipped: {e}"),
+                ));
             })
+            .ok()
+    }
+
+    /// Follow `$ref`s at the top of `value` (but not inside it), merging any
+    /// sibling keys over the target as OpenAPI 3.1 allows
+    fn resolve_shallow(&self, value: &JsonValue) -> JsonValue {
+        // Bounds reference chains so a self-referencing entry cannot loop forever
+        const MAX_HOPS: usize = 32;
+        let mut resolved = value.clone();
+        for _ in 0..MAX_HOPS {
+            let Some(map) = resolved.as_object() else {
+                break;
+            };
+            let Some(target) = map
+                .get("$ref")
+                .and_then(JsonValue::as_str)
+                .and_then(|ref_str| self.resolve_ref(ref_str))
+            else {
+                break;
+            };
+            let mut next = target.clone();
+            if let Some(obj) = next.as_object_mut() {
+                for (key, sibling) in map.iter().filter(|(k, _)| *k != "$ref") {
+                    obj.insert(key.clone(), sibling.clone());
+                }
+            }
+            resolved = next;
+        }
+        resolved
+    }
+
+    /// Resolve a local reference such as `#/components/schemas/Pet` to the value it points at
+    pub fn resolve_ref(&self, ref_str: &str) -> Option<&JsonValue> {
+        ref_str
+            .strip_prefix('#')
+            .and_then(|fragment| self.json.pointer(&fragment_to_pointer(fragment)))
+    }
+
+    /// Return a copy of `value` with every local `$ref` replaced by its target.
+    ///
+    /// A reference back into a schema that is still being expanded (a tree node
+    /// pointing at its children, say) is not inlined again. It becomes a
+    /// `#/$defs/<Name>` back-reference instead, and the definitions those
+    /// back-references need are returned alongside the inlined value so the
+    /// caller can attach them at the root of the schema document.
+    /// References that cannot be resolved are left in place.
+    ///
+    /// Alternatives of a discriminated `oneOf`/`anyOf` that were references get
+    /// an `x-discriminator-value`, as the reference that implied their tag value
+    /// is gone once inlined.
+    pub fn inline_refs(&self, value: &JsonValue) -> (JsonValue, JsonMap<String, JsonValue>) {
+        let mut defs = JsonMap::new();
+        let inlined = self.inline_refs_with_stack(value, &mut Vec::new(), &mut defs);
+        (inlined, defs)
+    }
+
+    /// Inline the refs of an OpenAPI object such as a parameter or request body,
+    /// attaching the `$defs` of recursive schemas to each of its `schema` fields.
+    fn inline_object_refs(&self, value: &JsonValue) -> JsonValue {
+        let (mut inlined, defs) = self.inline_refs(value);
+        if !defs.is_empty() {
+            attach_schema_defs(&mut inlined, &defs);
+        }
+        inlined
+    }
+
+    fn inline_refs_with_stack(
+        &self,
+        value: &JsonValue,
+        stack: &mut Vec<String>,
+        defs: &mut JsonMap<String, JsonValue>,
+    ) -> JsonValue {
+        match value {
+            JsonValue::Object(map) => {
+                if let Some(ref_str) = map.get("$ref").and_then(JsonValue::as_str) {
+                    let Some(target) = self.resolve_ref(ref_str) else {
+                        return value.clone();
+                    };
+                    if stack.iter().any(|seen| seen == ref_str) {
+                        let name = defs_name(ref_str);
+                        if !defs.contains_key(&name) {
+                            // Reserve the name first so the definition can refer to itself
+                            defs.insert(name.clone(), JsonValue::Null);
+                            let mut def_stack = vec![ref_str.to_string()];
+                            let def = self.inline_refs_with_stack(target, &mut def_stack, defs);
+                            defs.insert(name.clone(), def);
+                        }
+                        return json!({ "$ref": format!("#/$defs/{}", name.replace('~', "~0").replace('/', "~1")) });
+                    }
+                    stack.push(ref_str.to_string());
+                    let mut inlined = self.inline_refs_with_stack(target, stack, defs);
+                    stack.pop();
+                    // OpenAPI 3.1 allows keywords next to $ref; they refine the target
+                    if let Some(obj) = inlined.as_object_mut() {
+                        for (key, sibling) in map.iter().filter(|(k, _)| *k != "$ref") {
+                            obj.insert(
+                                key.clone(),
+                                self.inline_refs_with_stack(sibling, stack, defs),
+                            );
+                        }
+                    }
+                    return inlined;
+                }
+                let mut inlined: JsonMap<String, JsonValue> = map
+                    .iter()
+                    .map(|(k, v)| (k.clone(), self.inline_refs_with_stack(v, stack, defs)))
+                    .collect();
+                annotate_discriminator_values(map, &mut inlined);
+                JsonValue::Object(inlined)
+            }
+            JsonValue::Array(items) => JsonValue::Array(
+                items
+                    .iter()
+                    .map(|v| self.inline_refs_with_stack(v, stack, defs))
+                    .collect(),
+            ),
+            _ => value.clone(),
+        }
+    }
+
+    /// Extracts the effective parameters of an operation.
+    ///
+    /// Path-item parameters apply to every operation under the path; parameters
+    /// declared on the operation override them when both `name` and `in` match.
+    /// `$ref` entries (e.g. `#/components/parameters/Limit`) are resolved first.
+    fn extract_parameters(
+        &self,
+        location: &OperationLocation<'_>,
+        method_item: &JsonMap<String, JsonValue>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Option<Vec<OpenApiParameter>> {
+        let path_params = location.path_item.get("parameters");
+        let op_params = method_item.get("parameters");
+        if path_params.is_none() && op_params.is_none() {
+            return None;
+        }
+
+        let mut merged: Vec<OpenApiParameter> = Vec::new();
+        for (owner, params) in [
+            (location.item_pointer, path_params),
+            (location.pointer, op_params),
+        ] {
+            let Some(params) = params else {
+                continue;
+            };
+            let list_pointer = pointer_join(owner, "parameters");
+            let Some(params) = params.as_array() else {
+                diagnostics.push(Diagnostic::error(
+                    list_pointer,
+                    "parameters is not an array; skipped",
+                ));
+                continue;
+            };
+            for (index, param) in params.iter().enumerate() {
+                let param_pointer = pointer_join(&list_pointer, &index.to_string());
+                let mut param = self.inline_object_refs(param);
+                // An invalid extension is reported on its own, without losing the parameter
+                let mut mcp = McpParameterExtension::default();
+                if let Some(object) = param.as_object_mut() {
+                    mcp = parse_mcp_extension(object, &param_pointer, diagnostics);
+                    object.remove(MCP_EXTENSION);
+                }
+                let Some(mut param) = self.parse_object::<OpenApiParameter>(
+                    param,
+                    &param_pointer,
+                    "parameter",
+                    diagnostics,
+                ) else {
+                    continue;
+                };
+                param.mcp = mcp;
+                match merged
+                    .iter_mut()
+                    .find(|p| p.name == param.name && p.in_ == param.in_)
+                {
+                    Some(existing) => *existing = param,
+                    None => merged.push(param),
+                }
+            }
+        }
+        Some(merged)
     }
 
-    /// Extracts response definitions from an OpenAPI operation
+    /// Extracts response definitions from an OpenAPI operation, resolving
+    /// responses shared through `#/components/responses`
     fn extract_responses(
         &self,
-        get_item: &serde_json::Map<String, JsonValue>,
+        method_item: &JsonMap<String, JsonValue>,
+        pointer: &str,
+        diagnostics: &mut Vec<Diagnostic>,
     ) -> std::collections::HashMap<String, OpenApiResponse> {
-        get_item
-            .get("responses")
-            .and_then(JsonValue::as_object)
-            .map(|map| {
-                map.iter()
-                    .filter_map(|(k, v)| {
-                        serde_json::from_value(v.clone())
-                            .ok()
-                            .map(|resp| (k.clone(), resp))
-                    })
-                    .collect()
+        let Some(responses) = method_item.get("responses") else {
+            return Default::default();
+        };
+        let pointer = pointer_join(pointer, "responses");
+        let Some(responses) = responses.as_object() else {
+            diagnostics.push(Diagnostic::error(
+                pointer,
+                "responses is not an object; skipped",
+            ));
+            return Default::default();
+        };
+        responses
+            .iter()
+            .filter_map(|(status, response)| {
+                self.parse_object(
+                    self.inline_object_refs(response),
+                    &pointer_join(&pointer, status),
+                    "response",
+                    diagnostics,
+                )
+                .map(|response| (status.clone(), response))
             })
-            .unwrap_or_default()
+            .collect()
     }
 
     /// Extracts vendor extensions (x-* prefixed properties) from an OpenAPI operation
@@ -379,13 +710,13 @@ impl OpenApiContext {
 
     /// Extract row properties from properties JSON
     pub fn extract_row_properties(properties_json: &JsonValue) -> Vec<JsonValue> {
-        if let Some(data) = properties_json.get("data").and_then(JsonValue::as_object) {
-            if let Some(props) = data.get("properties").and_then(JsonValue::as_object) {
-                return props
-                    .iter()
-                    .map(|(k, v)| json!({"name": k, "schema": v}))
-                    .collect();
-            }
+        if let Some(data) = properties_json.get("data").and_then(JsonValue::as_object)
+            && let Some(props) = data.get("properties").and_then(JsonValue::as_object)
+        {
+            return props
+                .iter()
+                .map(|(k, v)| json!({"name": k, "schema": v}))
+                .collect();
         }
         if let Some(props) = properties_json.as_object() {
             return props
@@ -415,7 +746,7 @@ impl OpenApiContext {
                     .and_then(|s| s.get("description"))
                     .and_then(JsonValue::as_str)
                     .map(String::from);
-                let example = schema.and_then(|s| s.get("example")).cloned();
+                let example = schema.and_then(schema_example);
                 OpenApiPropertyInfo {
                     name: name.clone(),
                     title,
@@ -523,6 +854,9 @@ impl OpenApiContext {
         &self,
         schema: &JsonValue,
     ) -> crate::core::error::Result<(JsonValue, Option<String>)> {
+        // Inheritance through allOf contributes the properties of every member
+        let schema = &merge_all_of(schema);
+
         // Handle null or non-object schemas
         let schema_obj = match schema.as_object() {
             Some(obj) => obj,
@@ -541,10 +875,11 @@ impl OpenApiContext {
         }
 
         // Primitive types: return no properties
-        if let Some(typ) = schema_obj.get("type").and_then(JsonValue::as_str) {
-            if typ != "object" && typ != "array" {
-                return Ok((JsonValue::Null, None));
-            }
+        if let Some(typ) = schema_obj.get("type").and_then(JsonValue::as_str)
+            && typ != "object"
+            && typ != "array"
+        {
+            return Ok((JsonValue::Null, None));
         }
 
         // Handle $ref
@@ -565,26 +900,18 @@ impl OpenApiContext {
         };
 
         // Resolve the reference
-        let key = "#/components/schemas/";
-        if !ref_str.starts_with(key) {
-            return Err(Error::openapi(format!("Unexpected schema ref '{ref_str}'")));
-        }
-
-        let schema_name = &ref_str[key.len()..];
-        let schemas = self
-            .json
-            .get("components")
-            .and_then(JsonValue::as_object)
-            .and_then(|m| m.get("schemas"))
-            .and_then(JsonValue::as_object)
-            .ok_or_else(|| Error::openapi("No components.schemas section"))?;
-
-        let def = schemas
-            .get(schema_name)
-            .ok_or_else(|| Error::openapi(format!("Schema '{schema_name}' not found")))?;
-
+        let def = self
+            .resolve_ref(ref_str)
+            .ok_or_else(|| Error::openapi(format!("Unresolved schema ref '{ref_str}'")))?;
+        let schema_name = ref_str.rsplit('/').next().map(|name| {
+            fragment_to_pointer(name)
+                .replace("~1", "/")
+                .replace("~0", "~")
+        });
+
+        let def = merge_all_of(&self.inline_refs(def).0);
         let props = def.get("properties").cloned().unwrap_or(JsonValue::Null);
-        Ok((props, Some(schema_name.to_string())))
+        Ok((props, schema_name))
     }
 
     /// Extract request body properties from an operation
@@ -601,21 +928,19 @@ impl OpenApiContext {
             return Ok((serde_json::json!({}), None));
         };
 
-        // Extract content from request body
-        let content = request_body
-            .get("content")
-            .and_then(JsonValue::as_object)
-            .ok_or_else(|| Error::openapi("Request body has no content"))?;
-
         // Look for application/json content
-        let json_content = content
+        if request_body.content.is_empty() {
+            return Err(Error::openapi("Request body has no content"));
+        }
+        let json_content = request_body
+            .content
             .get("application/json")
-            .and_then(JsonValue::as_object)
             .ok_or_else(|| Error::openapi("Request body has no application/json content"))?;
 
         // Extract schema
         let schema = json_content
-            .get("schema")
+            .schema
+            .as_ref()
             .ok_or_else(|| Error::openapi("Request body content has no schema"))?;
 
         // Use the generic schema extraction method
@@ -623,12 +948,282 @@ impl OpenApiContext {
     }
 }
 
+/// Record the discriminator value of each referenced `oneOf`/`anyOf` alternative.
+///
+/// The value comes from the discriminator `mapping` when it lists the reference
+/// (by full reference or bare schema name), else it is the schema name.
+fn annotate_discriminator_values(
+    original: &JsonMap<String, JsonValue>,
+    inlined: &mut JsonMap<String, JsonValue>,
+) {
+    let Some(discriminator) = original.get("discriminator") else {
+        return;
+    };
+    let mapping = discriminator.get("mapping").and_then(JsonValue::as_object);
+    for key in ["oneOf", "anyOf"] {
+        let (Some(JsonValue::Array(before)), Some(JsonValue::Array(after))) =
+            (original.get(key), inlined.get_mut(key))
+        else {
+            continue;
+        };
+        for (alternative, expanded) in before.iter().zip(after.iter_mut()) {
+            let (Some(ref_str), Some(expanded)) = (
+                alternative.get("$ref").and_then(JsonValue::as_str),
+                expanded.as_object_mut(),
+            ) else {
+                continue;
+            };
+            let name = defs_name(ref_str);
+            let value = mapping
+                .into_iter()
+                .flatten()
+                .find(|(_, target)| target.as_str() == Some(ref_str) || **target == name)
+                .map_or(name.clone(), |(value, _)| value.clone());
+            expanded
+                .entry("x-discriminator-value")
+                .or_insert(JsonValue::String(value));
+        }
+    }
+}
+
+/// Merge the `allOf` members of a schema into a single schema.
+///
+/// `properties` of all members are combined and their `required` lists unioned;
+/// for any other keyword the schema's own value wins, then the earliest member's.
+/// Members are expected to be inlined already; nested `allOf`s are merged too.
+pub fn merge_all_of(schema: &JsonValue) -> JsonValue {
+    let Some(members) = schema.get("allOf").and_then(JsonValue::as_array) else {
+        return schema.clone();
+    };
+    let mut own = schema.clone();
+    if let Some(obj) = own.as_object_mut() {
+        obj.remove("allOf");
+    }
+
+    let mut merged = JsonMap::new();
+    let mut properties = JsonMap::new();
+    let mut required: Vec<JsonValue> = Vec::new();
+    for part in std::iter::once(own).chain(members.iter().map(merge_all_of)) {
+        let JsonValue::Object(part) = part else {
+            continue;
+        };
+        for (key, value) in part {
+            match (key.as_str(), value) {
+                ("properties", JsonValue::Object(props)) => {
+                    for (name, prop) in props {
+                        properties.entry(name).or_insert(prop);
+                    }
+                }
+                ("required", JsonValue::Array(names)) => {
+                    for name in names {
+                        if !required.contains(&name) {
+                            required.push(name);
+                        }
+                    }
+                }
+                (_, value) => {
+                    merged.entry(key).or_insert(value);
+                }
+            }
+        }
+    }
+    if !properties.is_empty() {
+        merged.insert("properties".to_string(), JsonValue::Object(properties));
+        merged
+            .entry("type")
+            .or_insert_with(|| JsonValue::String("object".to_string()));
+    }
+    if !required.is_empty() {
+        merged.insert("required".to_string(), JsonValue::Array(required));
+    }
+    JsonValue::Object(merged)
+}
+
+/// Name used under `$defs` for the target of `ref_str` (its last pointer segment)
+fn defs_name(ref_str: &str) -> String {
+    let last = ref_str.rsplit('/').next().unwrap_or(ref_str);
+    fragment_to_pointer(last)
+        .replace("~1", "/")
+        .replace("~0", "~")
+}
+
+/// Expand the `{variable}` placeholders of a server URL.
+///
+/// Each variable takes its value from `overrides` when given, else its default.
+/// Overrides must name a variable the server declares and, when the variable
+/// restricts its values with `enum`, be one of them.
+pub fn resolve_server_url(
+    server: &Server,
+    overrides: &BTreeMap<String, String>,
+) -> crate::core::error::Result<String> {
+    let declared = |name: &str| server.variables.as_ref().and_then(|vars| vars.get(name));
+    for (name, value) in overrides {
+        let Some(variable) = declared(name) else {
+            return Err(Error::openapi(format!(
+                "Server '{}' does not declare a variable named '{name}'",
+                server.url
+            )));
+        };
+        if !variable.enumeration.is_empty() && !variable.enumeration.contains(value) {
+            return Err(Error::openapi(format!(
+                "Value '{value}' for server variable '{name}' must be one of: {}",
+                variable.enumeration.join(", ")
+            )));
+        }
+    }
+
+    let mut url = String::with_capacity(server.url.len());
+    let mut rest = server.url.as_str();
+    while let Some(start) = rest.find('{') {
+        url.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            return Err(Error::openapi(format!(
+                "Server URL '{}' has an unclosed '{{'",
+                server.url
+            )));
+        };
+        let name = &rest[start + 1..start + len];
+        let value = overrides
+            .get(name)
+            .or_else(|| declared(name).map(|variable| &variable.default))
+            .ok_or_else(|| {
+                Error::openapi(format!(
+                    "Server URL '{}' uses variable '{name}', which is not declared",
+                    server.url
+                ))
+            })?;
+        url.push_str(value);
+        rest = &rest[start + len + 1..];
+    }
+    url.push_str(rest);
+    Ok(url)
+}
+
+/// Example value of a schema: `example`, or the first of the 3.1 `examples` array
+pub fn schema_example(schema: &JsonValue) -> Option<JsonValue> {
+    schema.get("example").cloned().or_else(|| {
+        schema
+            .get("examples")
+            .and_then(JsonValue::as_array)
+            .and_then(|examples| examples.first())
+            .cloned()
+    })
+}
+
+/// Attach `defs` to every `schema` field found in `value`
+fn attach_schema_defs(value: &mut JsonValue, defs: &JsonMap<String, JsonValue>) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == "schema"
+                    && let Some(schema) = child.as_object_mut()
+                {
+                    // Keep any $defs the schema declares itself (JSON Schema 2020-12)
+                    let schema_defs = schema
+                        .entry("$defs")
+                        .or_insert_with(|| JsonValue::Object(JsonMap::new()));
+                    if let Some(schema_defs) = schema_defs.as_object_mut() {
+                        for (name, def) in defs {
+                            schema_defs.insert(name.clone(), def.clone());
+                        }
+                    }
+                } else {
+                    attach_schema_defs(child, defs);
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items {
+                attach_schema_defs(item, defs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Parse the `x-mcp` extension of `object`, reporting an invalid one and
+/// falling back to no customization
+fn parse_mcp_extension<T: DeserializeOwned + Default>(
+    object: &JsonMap<String, JsonValue>,
+    pointer: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> T {
+    let Some(extension) = object.get(MCP_EXTENSION) else {
+        return T::default();
+    };
+    serde_json::from_value(extension.clone()).unwrap_or_else(|e| {
+        diagnostics.push(Diagnostic::error(
+            pointer_join(pointer, MCP_EXTENSION),
+            format!("Invalid {MCP_EXTENSION} extension; ignored: {e}"),
+        ));
+        T::default()
+    })
+}
+
+/// Warn about `$ref`s in `value` that do not resolve within `spec`.
+///
+/// `#/$defs/` references are skipped: they point into the schema's own
+/// definitions rather than the document.
+fn report_unresolved_refs(
+    spec: &OpenApiContext,
+    value: &JsonValue,
+    pointer: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map {
+                let child_pointer = pointer_join(pointer, key);
+                match (key.as_str(), child) {
+                    ("$ref", JsonValue::String(ref_str))
+                        if !ref_str.starts_with("#/$defs/")
+                            && spec.resolve_ref(ref_str).is_none() =>
+                    {
+                        diagnostics.push(Diagnostic::warning(
+                            child_pointer,
+                            format!("Unresolved $ref '{ref_str}' left in place"),
+                        ));
+                    }
+                    _ => report_unresolved_refs(spec, child, &child_pointer, diagnostics),
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                report_unresolved_refs(
+                    spec,
+                    item,
+                    &pointer_join(pointer, &index.to_string()),
+                    diagnostics,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Where an operation lives in the document, for building it and reporting problems
+#[derive(Clone, Copy)]
+struct OperationLocation<'a> {
+    path: &'a str,
+    method: &'a HttpMethod,
+    path_item: &'a JsonMap<String, JsonValue>,
+    /// JSON pointer to the path item
+    item_pointer: &'a str,
+    /// JSON pointer to the operation
+    pointer: &'a str,
+}
+
 /// Parsed OpenAPI operation for template rendering
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OpenApiOperation {
     /// Unique string used to identify the operation. The id MUST be unique among all operations described in the API.
     #[serde(rename = "operationId")]
     pub id: String,
+    /// Name of the MCP tool generated for the operation, set by
+    /// [`assign_tool_names`](crate::core::tool_names::assign_tool_names)
+    #[serde(default)]
+    pub tool_name: String,
     /// The path where this operation is defined (e.g., "/pet/findByStatus")
     pub path: String,
     /// The HTTP method for this operation
@@ -647,7 +1242,7 @@ pub struct OpenApiOperation {
     pub parameters: Option<Vec<OpenApiParameter>>,
     /// The request body applicable for this operation.
     #[serde(rename = "requestBody")]
-    pub request_body: Option<serde_json::Value>,
+    pub request_body: Option<OpenApiRequestBody>,
     /// The list of possible responses as they are returned from executing this operation.
     pub responses: std::collections::HashMap<String, OpenApiResponse>,
     /// A map of possible out-of band callbacks related to the parent operation.
@@ -655,24 +1250,28 @@ pub struct OpenApiOperation {
     /// Declares this operation to be deprecated. Consumers SHOULD refrain from usage of the declared operation.
     pub deprecated: Option<bool>,
     /// A declaration of which security mechanisms can be used for this operation.
-    pub security: Option<Vec<serde_json::Value>>,
+    /// Taken from the document's top-level `security` unless the operation declares
+    /// its own; an empty list means no security.
+    pub security: Option<Vec<SecurityRequirement>>,
     /// An alternative server array to service this operation.
     pub servers: Option<Vec<serde_json::Value>>,
+    /// The `x-mcp` extension, customizing the operation's tool
+    #[serde(
+        rename = "x-mcp",
+        default,
+        skip_serializing_if = "McpOperationExtension::is_empty"
+    )]
+    pub mcp: McpOperationExtension,
     /// Specification extensions (fields starting with `x-`).
     #[serde(flatten)]
     pub vendor_extensions: std::collections::HashMap<String, serde_json::Value>,
 }
 
-/// Info about a single OpenAPI parameter
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct OpenApiParameterInfo {
-    /// Name of the parameter as defined in the OpenAPI spec
-    pub name: String,
-    /// Optional description of the parameter
-    pub description: Option<String>,
-    /// Optional example value for the parameter
-    pub example: Option<JsonValue>,
-    // Note: Language-specific type info (e.g., rust_type) is injected by the builder, not stored here.
+impl OpenApiOperation {
+    /// JSON pointer to the operation, e.g. `/paths/~1pets/get`
+    pub fn pointer(&self) -> String {
+        pointer_join(&pointer_join("/paths", &self.path), self.method.as_str())
+    }
 }
 
 /// Info about a single response property
@@ -714,6 +1313,13 @@ pub struct OpenApiParameter {
     pub allow_reserved: Option<bool>,
     /// The schema defining the type used for the parameter.
     pub schema: Option<serde_json::Value>,
+    /// The `x-mcp` extension, customizing the parameter's tool argument
+    #[serde(
+        rename = "x-mcp",
+        default,
+        skip_serializing_if = "McpParameterExtension::is_empty"
+    )]
+    pub mcp: McpParameterExtension,
     /// Example of the parameter's potential value. The example SHOULD match the specified schema and encoding properties if present.
     pub example: Option<serde_json::Value>,
     /// Examples of the parameter's potential value. Each example SHOULD contain a value in the correct format as specified in the parameter encoding.
@@ -725,6 +1331,43 @@ pub struct OpenApiParameter {
     pub vendor_extensions: std::collections::HashMap<String, serde_json::Value>,
 }
 
+/// The request body of an operation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiRequestBody {
+    /// A brief description of the request body. CommonMark syntax MAY be used for rich text representation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The content of the request body. The key is a media type, and the value describes it.
+    pub content: std::collections::HashMap<String, OpenApiMediaType>,
+    /// Determines if the request body is required in the request. Defaults to false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    /// Specification extensions (fields starting with `x-`).
+    #[serde(flatten)]
+    pub vendor_extensions: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// Schema and examples for one media type of a request or response body.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenApiMediaType {
+    /// The schema defining the content. It stays raw JSON Schema, since the typed
+    /// `openapiv3` schema model cannot represent OpenAPI 3.1 keywords.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    /// Example of the media type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    /// Examples of the media type, keyed by name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<std::collections::HashMap<String, serde_json::Value>>,
+    /// Encoding of individual properties, for `multipart` and `application/x-www-form-urlencoded` bodies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<std::collections::HashMap<String, serde_json::Value>>,
+    /// Specification extensions (fields starting with `x-`).
+    #[serde(flatten)]
+    pub vendor_extensions: std::collections::HashMap<String, serde_json::Value>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OpenApiResponse {
     /// A short description of the response. CommonMark syntax MAY be used for rich text representation.
@@ -732,7 +1375,7 @@ pub struct OpenApiResponse {
     /// Maps a header name to its definition. The key is the name of the header, and the value describes it.
     pub headers: Option<std::collections::HashMap<String, serde_json::Value>>,
     /// A map containing descriptions of potential response payloads. The key is a media type, and the value describes it.
-    pub content: Option<std::collections::HashMap<String, serde_json::Value>>,
+    pub content: Option<std::collections::HashMap<String, OpenApiMediaType>>,
     /// A map of operations links that can be followed from the response. The key is the link name, the value describes the link.
     pub links: Option<std::collections::HashMap<String, serde_json::Value>>,
     /// Specification extensions (fields starting with `x-`).
@@ -743,6 +1386,7 @@ pub struct OpenApiResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::diagnostics::Severity;
     use serde_json::json;
     use tempfile::tempdir;
 
@@ -830,7 +1474,7 @@ mod tests {
         "#;
         tokio::fs::write(&file_path, json_content).await?;
 
-        let spec = OpenApiContext::from_file(&file_path).await?;
+        let spec = OpenApiContext::from_file_or_url(file_path.to_str().unwrap()).await?;
         assert_eq!(spec.title(), Some("Test API Async"));
         assert_eq!(spec.version(), Some("2.0.0"));
         assert_eq!(
@@ -941,4 +1585,478 @@ mod tests {
             .collect();
         assert_eq!(names, vec!["p".to_string(), "q".to_string()]);
     }
+
+    #[tokio::test]
+    async fn test_parse_operations_inlines_request_body_refs() {
+        let spec = OpenApiContext {
+            json: json!({
+                "paths": {"/pet": {"post": {
+                    "operationId": "addPet",
+                    "requestBody": {"$ref": "#/components/requestBodies/Pet"},
+                    "responses": {}
+                }}},
+                "components": {
+                    "requestBodies": {"Pet": {"content": {"application/json": {
+                        "schema": {"$ref": "#/components/schemas/Pet"}
+                    }}}},
+                    "schemas": {
+                        "Pet": {"type": "object", "properties": {
+                            "name": {"type": "string"},
+                            "category": {"$ref": "#/components/schemas/Category"}
+                        }},
+                        "Category": {"type": "object", "properties": {
+                            "parent": {"$ref": "#/components/schemas/Category"}
+                        }}
+                    }
+                }
+            }),
+        };
+
+        let ops = spec.parse_operations().await.unwrap();
+        let body = ops[0].request_body.as_ref().unwrap();
+        let schema = body.content["application/json"].schema.as_ref().unwrap();
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        let category = &schema["properties"]["category"];
+        assert_eq!(category["type"], "object");
+        // Self-references point back at a $defs entry instead of expanding forever
+        assert_eq!(category["properties"]["parent"]["$ref"], "#/$defs/Category");
+        assert_eq!(
+            schema["$defs"]["Category"]["properties"]["parent"]["$ref"],
+            "#/$defs/Category"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_operations_merges_path_and_operation_parameters() {
+        let spec = OpenApiContext {
+            json: json!({
+                "paths": {"/pets/{petId}": {
+                    "parameters": [
+                        {"name": "petId", "in": "path", "required": true, "description": "path level"},
+                        {"$ref": "#/components/parameters/Trace"}
+                    ],
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            {"name": "petId", "in": "path", "required": true, "description": "operation level"},
+                            {"name": "petId", "in": "query"},
+                            {"$ref": "#/components/parameters/Limit"}
+                        ],
+                        "responses": {}
+                    },
+                    "delete": {"operationId": "deletePet", "responses": {}}
+                }},
+                "components": {"parameters": {
+                    "Limit": {"name": "limit", "in": "query", "schema": {"$ref": "#/components/schemas/Count"}},
+                    "Trace": {"name": "X-Trace", "in": "header"}
+                }, "schemas": {"Count": {"type": "integer"}}}
+            }),
+        };
+
+        let ops = spec.parse_operations().await.unwrap();
+        let get = ops.iter().find(|op| op.id == "getPet").unwrap();
+        let params = get.parameters.as_ref().unwrap();
+        let keys: Vec<(&str, &str)> = params
+            .iter()
+            .map(|p| (p.name.as_str(), p.in_.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("petId", "path"),
+                ("X-Trace", "header"),
+                ("petId", "query"),
+                ("limit", "query")
+            ]
+        );
+        assert_eq!(params[0].description.as_deref(), Some("operation level"));
+        assert_eq!(params[3].schema.as_ref().unwrap()["type"], "integer");
+
+        // Operations without their own parameters inherit the path-level ones
+        let delete = ops.iter().find(|op| op.id == "deletePet").unwrap();
+        assert_eq!(delete.parameters.as_ref().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_parse_operations_reads_mcp_extensions() {
+        let spec = OpenApiContext {
+            json: json!({
+                "paths": {"/things": {
+                    "get": {
+                        "operationId": "listThings",
+                        "x-mcp": {"name": "search_things", "annotations": {"readOnly": true}},
+                        "x-rate-limit": 10,
+                        "parameters": [
+                            {"$ref": "#/components/parameters/Tenant"},
+                            {"name": "limit", "in": "query", "x-mcp": {"default": 20}}
+                        ],
+                        "responses": {}
+                    },
+                    "post": {
+                        "operationId": "addThing",
+                        "x-mcp": {"hidden": "yes"},
+                        "responses": {}
+                    },
+                    "delete": {
+                        "operationId": "purgeThings",
+                        "x-mcp": {"resource": true},
+                        "responses": {}
+                    }
+                }},
+                "components": {"parameters": {
+                    "Tenant": {"name": "tenant", "in": "query", "x-mcp": {"config": "tenant"}}
+                }}
+            }),
+        };
+
+        let (ops, diagnostics) = spec.parse_operations_with_diagnostics().await.unwrap();
+        let get = ops.iter().find(|op| op.id == "listThings").unwrap();
+        assert_eq!(get.mcp.name.as_deref(), Some("search_things"));
+        assert_eq!(get.mcp.annotations.as_ref().unwrap().read_only, Some(true));
+        // Only other extensions are left as vendor extensions
+        assert_eq!(
+            get.vendor_extensions.keys().collect::<Vec<_>>(),
+            ["x-rate-limit"]
+        );
+
+        let params = get.parameters.as_ref().unwrap();
+        assert_eq!(params[0].mcp.config.as_deref(), Some("tenant"));
+        assert_eq!(params[1].mcp.default, Some(json!(20)));
+        assert!(!params[1].vendor_extensions.contains_key(MCP_EXTENSION));
+
+        // An invalid extension is reported and ignored
+        let post = ops.iter().find(|op| op.id == "addThing").unwrap();
+        assert!(post.mcp.is_empty());
+        assert_eq!(diagnostics[0].pointer, "/paths/~1things/post/x-mcp");
+
+        // Only GET operations can be resources
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[1].pointer,
+            "/paths/~1things/delete/x-mcp/resource"
+        );
+    }
+
+    #[test]
+    fn test_inline_refs_breaks_mutual_recursion() {
+        let spec = OpenApiContext {
+            json: json!({"components": {"schemas": {
+                "Employee": {"type": "object", "properties": {
+                    "team": {"$ref": "#/components/schemas/Team"}
+                }},
+                "Team": {"type": "object", "properties": {
+                    "members": {"type": "array", "items": {"$ref": "#/components/schemas/Employee"}}
+                }}
+            }}}),
+        };
+
+        let (inlined, defs) = spec.inline_refs(&json!({"$ref": "#/components/schemas/Employee"}));
+        let members = &inlined["properties"]["team"]["properties"]["members"];
+        assert_eq!(members["items"]["$ref"], "#/$defs/Employee");
+        let team = &defs["Employee"]["properties"]["team"];
+        assert_eq!(
+            team["properties"]["members"]["items"]["$ref"],
+            "#/$defs/Employee"
+        );
+        assert_eq!(defs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_parse_openapi_3_1_fixture() -> crate::core::error::Result<()> {
+        let spec = OpenApiContext::from_file_or_url(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/openapi/petstore.openapi.v31.json"
+        ))
+        .await?;
+        assert!(spec.is_v3_1());
+
+        let ops = spec.parse_operations().await?;
+        let mut ids: Vec<&str> = ops.iter().map(|op| op.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["createPet", "getPet", "listPets"]);
+
+        let create = ops.iter().find(|op| op.id == "createPet").unwrap();
+        let schema = create.request_body.as_ref().unwrap().content["application/json"]
+            .schema
+            .as_ref()
+            .unwrap();
+        // Keywords next to $ref are kept, and refs into a schema's own $defs resolve
+        assert_eq!(schema["description"], "Pet to add to the store");
+        assert_eq!(
+            schema["properties"]["tag"]["properties"]["label"]["type"],
+            "string"
+        );
+        assert_eq!(
+            schema["properties"]["location"]["prefixItems"][0]["type"],
+            "number"
+        );
+
+        let get = ops.iter().find(|op| op.id == "getPet").unwrap();
+        let pet_id = &get.parameters.as_ref().unwrap()[0];
+        assert_eq!(
+            schema_example(pet_id.schema.as_ref().unwrap()),
+            Some(json!("rex-1"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parse_operations_paths_optional_in_3_1() {
+        let spec = OpenApiContext {
+            json: json!({"openapi": "3.1.0", "webhooks": {}}),
+        };
+        assert!(spec.parse_operations().await.unwrap().is_empty());
+
+        let spec = OpenApiContext {
+            json: json!({"openapi": "3.0.3"}),
+        };
+        assert!(spec.parse_operations().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parse_swagger_2_fixture() -> crate::core::error::Result<()> {
+        let spec = OpenApiContext::from_file_or_url(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/openapi/petstore.swagger.v2.json"
+        ))
+        .await?;
+        assert_eq!(spec.json["openapi"], "3.0.3");
+        assert_eq!(
+            spec.base_path(),
+            Some("https://petstore.swagger.io/v2".to_string())
+        );
+
+        let ops = spec.parse_operations().await?;
+        let add = ops.iter().find(|op| op.id == "addPet").unwrap();
+        assert!(add.parameters.as_ref().is_none_or(|p| p.is_empty()));
+        let schema = add.request_body.as_ref().unwrap().content["application/json"]
+            .schema
+            .as_ref()
+            .unwrap();
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+
+        let upload = ops.iter().find(|op| op.id == "uploadFile").unwrap();
+        let form = upload.request_body.as_ref().unwrap().content["multipart/form-data"]
+            .schema
+            .as_ref()
+            .unwrap();
+        assert_eq!(form["properties"]["file"]["format"], "binary");
+
+        let find = ops.iter().find(|op| op.id == "findPetsByStatus").unwrap();
+        let status = &find.parameters.as_ref().unwrap()[0];
+        assert_eq!(status.schema.as_ref().unwrap()["type"], "array");
+
+        let get = ops.iter().find(|op| op.id == "getPetById").unwrap();
+        let content = get.responses["200"].content.as_ref().unwrap();
+        // The converted `#/components/schemas/Pet` reference resolves and is inlined
+        let pet = content["application/xml"].schema.as_ref().unwrap();
+        assert!(pet.get("$ref").is_none());
+        assert_eq!(pet["properties"]["name"]["type"], "string");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parse_operations_reports_malformed_parts() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "paths": {
+                    "/pets/{id}": {
+                        "get": {
+                            "operationId": "getPet",
+                            "parameters": [
+                                {"name": "id", "in": "path", "required": true, "schema": {"type": "string"}},
+                                {"name": "broken", "schema": {"type": "string"}},
+                                {"$ref": "#/components/parameters/Missing"},
+                                {"name": "filter", "in": "query", "schema": {"$ref": "#/components/schemas/Gone"}}
+                            ],
+                            "requestBody": {"description": "no content"},
+                            "responses": {
+                                "200": {"$ref": "#/components/responses/Pet"},
+                                "404": "not found"
+                            },
+                            "security": [{"api_key": []}]
+                        }
+                    }
+                },
+                "components": {
+                    "responses": {
+                        "Pet": {
+                            "description": "A pet",
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                        }
+                    },
+                    "schemas": {"Pet": {"type": "object"}},
+                    "securitySchemes": {
+                        "api_key": {"type": "apiKey", "name": "X-Key", "in": "header"},
+                        "weird": {"type": "carrier-pigeon"}
+                    }
+                }
+            }),
+        };
+
+        let (ops, diagnostics) = spec.parse_operations_with_diagnostics().await.unwrap();
+        let op = &ops[0];
+        let names: Vec<&str> = op
+            .parameters
+            .iter()
+            .flatten()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["id", "filter"]);
+        assert!(op.request_body.is_none());
+        assert_eq!(op.responses["200"].description.as_deref(), Some("A pet"));
+        assert!(!op.responses.contains_key("404"));
+        assert_eq!(
+            op.security.as_ref().unwrap()[0]["api_key"],
+            Vec::<String>::new()
+        );
+
+        let at = |pointer: &str| {
+            diagnostics
+                .iter()
+                .find(|d| d.pointer == pointer)
+                .unwrap_or_else(|| panic!("no diagnostic at {pointer}: {diagnostics:#?}"))
+        };
+        let base = "/paths/~1pets~1{id}/get";
+        assert!(
+            at(&format!("{base}/parameters/1"))
+                .message
+                .contains("missing field `in`")
+        );
+        assert!(
+            at(&format!("{base}/parameters/2"))
+                .message
+                .contains("#/components/parameters/Missing")
+        );
+        let nested = at(&format!("{base}/parameters/3/schema/$ref"));
+        assert_eq!(nested.severity, Severity::Warning);
+        assert!(
+            at(&format!("{base}/requestBody"))
+                .message
+                .contains("content")
+        );
+        assert_eq!(
+            at(&format!("{base}/responses/404")).severity,
+            Severity::Error
+        );
+        assert_eq!(diagnostics.len(), 5);
+
+        let (schemes, diagnostics) = spec.security_schemes();
+        assert!(matches!(schemes["api_key"], SecurityScheme::APIKey { .. }));
+        assert!(!schemes.contains_key("weird"));
+        assert_eq!(diagnostics[0].pointer, "/components/securitySchemes/weird");
+    }
+
+    #[tokio::test]
+    async fn test_operations_inherit_document_security() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "security": [{"oauth": ["read"]}, {}],
+                "paths": {
+                    "/health": {"get": {"operationId": "health", "security": [], "responses": {}}},
+                    "/pets": {
+                        "get": {"operationId": "listPets", "responses": {}},
+                        "post": {
+                            "operationId": "createPet",
+                            "security": [{"oauth": ["write"]}],
+                            "responses": {}
+                        }
+                    }
+                }
+            }),
+        };
+        let ops = spec.parse_operations().await.unwrap();
+        let security = |id: &str| {
+            ops.iter()
+                .find(|op| op.id == id)
+                .and_then(|op| op.security.clone())
+                .unwrap()
+        };
+        assert!(security("health").is_empty());
+        let inherited = security("listPets");
+        assert_eq!(inherited[0]["oauth"], ["read"]);
+        assert!(inherited[1].is_empty());
+        assert_eq!(security("createPet")[0]["oauth"], ["write"]);
+    }
+
+    #[test]
+    fn test_servers_resolve_variables() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "servers": [
+                    {
+                        "url": "https://{region}.api.example.com/{version}",
+                        "description": "Regional",
+                        "variables": {
+                            "region": {"default": "us", "enum": ["us", "eu"]},
+                            "version": {"default": "v1"}
+                        }
+                    },
+                    {"description": "missing url"},
+                    {"url": "https://{tenant}.example.com"}
+                ],
+                "paths": {}
+            }),
+        };
+        assert_eq!(
+            spec.base_path().as_deref(),
+            Some("https://us.api.example.com/v1")
+        );
+
+        let (servers, diagnostics) = spec.servers();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pointer, "/servers/1");
+
+        let overrides = BTreeMap::from([("region".to_string(), "eu".to_string())]);
+        assert_eq!(
+            resolve_server_url(&servers[0], &overrides).unwrap(),
+            "https://eu.api.example.com/v1"
+        );
+        let invalid = BTreeMap::from([("region".to_string(), "ap".to_string())]);
+        assert!(resolve_server_url(&servers[0], &invalid).is_err());
+        let unknown = BTreeMap::from([("stage".to_string(), "prod".to_string())]);
+        assert!(resolve_server_url(&servers[0], &unknown).is_err());
+        // `{tenant}` is used without being declared
+        assert!(resolve_server_url(&servers[1], &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_merge_all_of_and_discriminator_values() {
+        let spec = OpenApiContext {
+            json: json!({"components": {"schemas": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {"name": {"type": "string"}, "petType": {"type": "string"}}
+                },
+                "Cat": {"allOf": [
+                    {"$ref": "#/components/schemas/Pet"},
+                    {"required": ["petType"], "properties": {"lives": {"type": "integer"}}}
+                ]},
+                "Dog": {"allOf": [{"$ref": "#/components/schemas/Pet"}]}
+            }}}),
+        };
+
+        let (cat, _) = spec.inline_refs(&json!({"$ref": "#/components/schemas/Cat"}));
+        let merged = merge_all_of(&cat);
+        assert_eq!(merged["type"], "object");
+        assert_eq!(merged["required"], json!(["name", "petType"]));
+        let names: Vec<&String> = merged["properties"].as_object().unwrap().keys().collect();
+        assert_eq!(names, ["lives", "name", "petType"]);
+        assert!(merged.get("allOf").is_none());
+
+        let (union, _) = spec.inline_refs(&json!({
+            "oneOf": [
+                {"$ref": "#/components/schemas/Cat"},
+                {"$ref": "#/components/schemas/Dog"}
+            ],
+            "discriminator": {"propertyName": "petType", "mapping": {"kitty": "Cat"}}
+        }));
+        assert_eq!(union["oneOf"][0]["x-discriminator-value"], "kitty");
+        assert_eq!(union["oneOf"][1]["x-discriminator-value"], "Dog");
+    }
 }
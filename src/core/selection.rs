@@ -0,0 +1,184 @@
+//! Selection of the operations a generated server exposes.
+//!
+//! [`OperationSelection`] is built from the include/exclude lists of a
+//! [`Config`]: operation ids, tags, path globs and HTTP methods. An operation
+//! is selected when it matches every kind of include filter that was given and
+//! none of the exclude filters.
+
+// Internal imports (std, crate)
+use crate::core::config::Config;
+use crate::core::error::{Error, Result};
+use crate::core::openapi::{HttpMethod, OpenApiOperation};
+
+// External imports (alphabetized)
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Compiled operation filters of a [`Config`]
+#[derive(Debug, Clone)]
+pub struct OperationSelection {
+    include_operations: Vec<String>,
+    exclude_operations: Vec<String>,
+    include_tags: Vec<String>,
+    exclude_tags: Vec<String>,
+    include_paths: Option<GlobSet>,
+    exclude_paths: Option<GlobSet>,
+    include_methods: Vec<HttpMethod>,
+    exclude_methods: Vec<HttpMethod>,
+}
+
+impl OperationSelection {
+    /// Compile the filters of `config`, failing on an invalid path glob.
+    ///
+    /// With `include_all` set, only the exclude filters apply.
+    pub fn new(config: &Config) -> Result<Self> {
+        let includes = |list: &Vec<String>| {
+            if config.include_all {
+                Vec::new()
+            } else {
+                list.clone()
+            }
+        };
+        Ok(Self {
+            include_operations: includes(&config.include_operations),
+            exclude_operations: config.exclude_operations.clone(),
+            include_tags: includes(&config.include_tags),
+            exclude_tags: config.exclude_tags.clone(),
+            include_paths: glob_set(&includes(&config.include_paths))?,
+            exclude_paths: glob_set(&config.exclude_paths)?,
+            include_methods: if config.include_all {
+                Vec::new()
+            } else {
+                config.include_methods.clone()
+            },
+            exclude_methods: config.exclude_methods.clone(),
+        })
+    }
+
+    /// Whether `op` is part of the generated server.
+    ///
+    /// Operation filters accept either the `operationId` or the tool name.
+    pub fn selects(&self, op: &OpenApiOperation) -> bool {
+        let named = |names: &[String]| names.iter().any(|n| *n == op.id || *n == op.tool_name);
+        let tags = op.tags.as_deref().unwrap_or_default();
+        let tagged = |wanted: &[String]| tags.iter().any(|tag| wanted.contains(tag));
+
+        let included = (self.include_operations.is_empty() || named(&self.include_operations))
+            && (self.include_tags.is_empty() || tagged(&self.include_tags))
+            && self
+                .include_paths
+                .as_ref()
+                .is_none_or(|globs| globs.is_match(&op.path))
+            && (self.include_methods.is_empty() || self.include_methods.contains(&op.method));
+        let excluded = named(&self.exclude_operations)
+            || tagged(&self.exclude_tags)
+            || self
+                .exclude_paths
+                .as_ref()
+                .is_some_and(|globs| globs.is_match(&op.path))
+            || self.exclude_methods.contains(&op.method);
+        included && !excluded
+    }
+}
+
+/// Compile path globs, where `*` stays within one path segment and `**` spans
+/// several (`/pets/*` matches `/pets/{petId}` but not `/pets/{petId}/photos`)
+fn glob_set(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(path_glob(pattern)?);
+    }
+    let set = builder
+        .build()
+        .map_err(|e| Error::config(format!("Invalid path globs: {e}")))?;
+    Ok(Some(set))
+}
+
+fn path_glob(pattern: &str) -> Result<Glob> {
+    // Braces are path template syntax (`{petId}`), not glob alternatives
+    let escaped = pattern.replace('{', "[{]").replace('}', "[}]");
+    GlobBuilder::new(&escaped)
+        .literal_separator(true)
+        .build()
+        .map_err(|e| Error::config(format!("Invalid path glob '{pattern}': {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn operation(id: &str, method: &str, path: &str, tags: &[&str]) -> OpenApiOperation {
+        let mut op: OpenApiOperation = serde_json::from_value(json!({
+            "operationId": id,
+            "path": path,
+            "method": method,
+            "tags": tags,
+            "responses": {}
+        }))
+        .unwrap();
+        op.tool_name = crate::core::utils::to_snake_case(id);
+        op
+    }
+
+    fn selected(config: &Config, operations: &[OpenApiOperation]) -> Vec<String> {
+        let selection = OperationSelection::new(config).unwrap();
+        operations
+            .iter()
+            .filter(|op| selection.selects(op))
+            .map(|op| op.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_selection_combines_filters() {
+        let operations = vec![
+            operation("listPets", "get", "/pets", &["pets"]),
+            operation("addPet", "post", "/pets", &["pets"]),
+            operation("getPet", "get", "/pets/{petId}", &["pets"]),
+            operation("listPhotos", "get", "/pets/{petId}/photos", &["photos"]),
+            operation("getStatus", "get", "/status", &[]),
+        ];
+        let mut config = Config::new("test", "openapi.json", "out");
+        assert_eq!(selected(&config, &operations).len(), 5);
+
+        config.include_tags = vec!["pets".to_string()];
+        config.exclude_methods = vec![HttpMethod::Post];
+        assert_eq!(selected(&config, &operations), ["listPets", "getPet"]);
+
+        config.include_paths = vec!["/pets/*".to_string()];
+        assert_eq!(selected(&config, &operations), ["getPet"]);
+
+        // Include filters are ignored when everything is included
+        config.include_all = true;
+        assert_eq!(
+            selected(&config, &operations),
+            ["listPets", "getPet", "listPhotos", "getStatus"]
+        );
+
+        let mut config = Config::new("test", "openapi.json", "out");
+        config.include_paths = vec!["/pets/{petId}".to_string(), "/status".to_string()];
+        assert_eq!(selected(&config, &operations), ["getPet", "getStatus"]);
+
+        let mut config = Config::new("test", "openapi.json", "out");
+        config.include_paths = vec!["/pets/**".to_string()];
+        config.exclude_operations = vec!["get_pet".to_string()];
+        config.include_methods = vec![HttpMethod::Get];
+        assert_eq!(selected(&config, &operations), ["listPhotos"]);
+
+        config.include_operations = vec!["getStatus".to_string()];
+        assert!(selected(&config, &operations).is_empty());
+    }
+
+    #[test]
+    fn test_selection_rejects_invalid_glob() {
+        let mut config = Config::new("test", "openapi.json", "out");
+        config.exclude_paths = vec!["/pets/[".to_string()];
+        assert!(matches!(
+            OperationSelection::new(&config),
+            Err(Error::Config(_))
+        ));
+    }
+}
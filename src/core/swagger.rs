@@ -0,0 +1,926 @@
+//! Upgrade of Swagger 2.0 documents to OpenAPI 3.0.
+//!
+//! Everything downstream of [`OpenApiContext`](crate::core::openapi::OpenApiContext)
+//! reads OpenAPI 3.x structures (`servers`, `requestBody`, response `content`,
+//! `components`). Swagger 2.0 expresses the same information differently, so a
+//! 2.0 document is rewritten into an equivalent 3.0 document when it is loaded:
+//!
+//! - `host`, `basePath` and `schemes` become `servers`
+//! - `definitions`, `parameters`, `responses` and `securityDefinitions` move
+//!   under `components`, and `$ref`s pointing at them are rewritten
+//! - `in: body` and `in: formData` parameters become a `requestBody` whose media
+//!   types come from `consumes`
+//! - response `schema`s become `content` keyed by the `produces` media types
+//! - parameter `type`/`format`/`items` move into a `schema`, and
+//!   `collectionFormat` becomes `style`/`explode`
+
+// Internal imports (std, crate)
+use crate::core::openapi::HttpMethod;
+
+// External imports (alphabetized)
+use serde_json::{Map as JsonMap, Value as JsonValue, json};
+
+/// OpenAPI version emitted for upgraded documents
+const UPGRADED_VERSION: &str = "3.0.3";
+
+/// Media type assumed when neither the operation nor the document declares one
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+const MULTIPART_FORM: &str = "multipart/form-data";
+
+/// Root keys that have no direct OpenAPI 3 counterpart and are consumed by the upgrade
+const SWAGGER_ONLY_KEYS: &[&str] = &[
+    "swagger",
+    "host",
+    "basePath",
+    "schemes",
+    "consumes",
+    "produces",
+    "definitions",
+    "parameters",
+    "responses",
+    "securityDefinitions",
+];
+
+/// Parameter fields that describe the value and belong in the OpenAPI 3 `schema`
+const PARAMETER_SCHEMA_KEYS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "enum",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "multipleOf",
+];
+
+/// Whether the document declares `swagger: "2.0"`
+pub fn is_swagger_2(doc: &JsonValue) -> bool {
+    doc.get("swagger")
+        .and_then(JsonValue::as_str)
+        .is_some_and(|v| v.starts_with("2."))
+}
+
+/// Convert a Swagger 2.0 document into the equivalent OpenAPI 3.0 document.
+///
+/// Documents that are not Swagger 2.0 are returned unchanged.
+pub fn upgrade(doc: JsonValue) -> JsonValue {
+    if !is_swagger_2(&doc) {
+        return doc;
+    }
+    let Some(root) = doc.as_object() else {
+        return doc;
+    };
+
+    let upgrader = Upgrader {
+        root,
+        consumes: media_types(root.get("consumes")),
+        produces: media_types(root.get("produces")),
+    };
+
+    let mut out = JsonMap::new();
+    out.insert("openapi".to_string(), json!(UPGRADED_VERSION));
+    for (key, value) in root {
+        if SWAGGER_ONLY_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let value = match key.as_str() {
+            "paths" => upgrader.paths(value),
+            // `info`, `tags`, `externalDocs` and vendor extensions hold no schemas
+            _ => value.clone(),
+        };
+        out.insert(key.clone(), value);
+    }
+
+    let servers = upgrader.servers();
+    if !servers.is_empty() {
+        out.insert("servers".to_string(), JsonValue::Array(servers));
+    }
+    let components = upgrader.components();
+    if !components.is_empty() {
+        out.insert("components".to_string(), JsonValue::Object(components));
+    }
+    out.entry("paths").or_insert_with(|| json!({}));
+
+    JsonValue::Object(out)
+}
+
+struct Upgrader<'a> {
+    root: &'a JsonMap<String, JsonValue>,
+    /// Document-wide `consumes`, used when an operation declares none
+    consumes: Option<Vec<String>>,
+    /// Document-wide `produces`, used when an operation declares none
+    produces: Option<Vec<String>>,
+}
+
+impl Upgrader<'_> {
+    /// Build `servers` from `host`, `basePath` and `schemes`.
+    ///
+    /// `https` is listed first when offered, since the first server is the
+    /// one generated code talks to.
+    fn servers(&self) -> Vec<JsonValue> {
+        let base_path = self
+            .root
+            .get("basePath")
+            .and_then(JsonValue::as_str)
+            .unwrap_or("");
+        let Some(host) = self.root.get("host").and_then(JsonValue::as_str) else {
+            // Without a host the API is served from wherever the spec lives
+            return if base_path.is_empty() {
+                Vec::new()
+            } else {
+                vec![json!({ "url": base_path })]
+            };
+        };
+
+        let mut schemes: Vec<&str> = self
+            .root
+            .get("schemes")
+            .and_then(JsonValue::as_array)
+            .map(|schemes| schemes.iter().filter_map(JsonValue::as_str).collect())
+            .unwrap_or_default();
+        if schemes.is_empty() {
+            schemes.push("https");
+        }
+        schemes.sort_by_key(|scheme| *scheme != "https");
+
+        schemes
+            .into_iter()
+            .map(|scheme| json!({ "url": format!("{scheme}://{host}{base_path}") }))
+            .collect()
+    }
+
+    fn components(&self) -> JsonMap<String, JsonValue> {
+        let mut components = JsonMap::new();
+
+        if let Some(definitions) = self.root.get("definitions").and_then(JsonValue::as_object) {
+            let schemas = definitions
+                .iter()
+                .map(|(name, schema)| (name.clone(), convert_schema(schema)))
+                .collect();
+            components.insert("schemas".to_string(), JsonValue::Object(schemas));
+        }
+
+        // Body and form parameters are inlined into each operation's requestBody
+        // instead, since OpenAPI 3 has no parameter location for them
+        if let Some(parameters) = self.root.get("parameters").and_then(JsonValue::as_object) {
+            let parameters: JsonMap<_, _> = parameters
+                .iter()
+                .filter(|(_, param)| !is_body_parameter(param))
+                .map(|(name, param)| (name.clone(), convert_parameter(param)))
+                .collect();
+            if !parameters.is_empty() {
+                components.insert("parameters".to_string(), JsonValue::Object(parameters));
+            }
+        }
+
+        if let Some(responses) = self.root.get("responses").and_then(JsonValue::as_object) {
+            let produces = self.produces.clone().unwrap_or_else(default_media_types);
+            let responses = responses
+                .iter()
+                .map(|(name, response)| (name.clone(), convert_response(response, &produces)))
+                .collect();
+            components.insert("responses".to_string(), JsonValue::Object(responses));
+        }
+
+        if let Some(definitions) = self
+            .root
+            .get("securityDefinitions")
+            .and_then(JsonValue::as_object)
+        {
+            let schemes = definitions
+                .iter()
+                .map(|(name, scheme)| (name.clone(), convert_security_scheme(scheme)))
+                .collect();
+            components.insert("securitySchemes".to_string(), JsonValue::Object(schemes));
+        }
+
+        components
+    }
+
+    fn paths(&self, paths: &JsonValue) -> JsonValue {
+        let Some(paths) = paths.as_object() else {
+            return paths.clone();
+        };
+        let paths = paths
+            .iter()
+            .map(|(path, item)| (path.clone(), self.path_item(item)))
+            .collect();
+        JsonValue::Object(paths)
+    }
+
+    fn path_item(&self, item: &JsonValue) -> JsonValue {
+        let Some(item) = item.as_object() else {
+            return item.clone();
+        };
+
+        let path_params: Vec<&JsonValue> = item
+            .get("parameters")
+            .and_then(JsonValue::as_array)
+            .map(|params| params.iter().map(|p| self.resolve_parameter(p)).collect())
+            .unwrap_or_default();
+
+        let mut out = JsonMap::new();
+        for (key, value) in item {
+            if key == "parameters" {
+                let params: Vec<_> = value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|p| !is_body_parameter(self.resolve_parameter(p)))
+                    .map(convert_parameter)
+                    .collect();
+                if !params.is_empty() {
+                    out.insert(key.clone(), JsonValue::Array(params));
+                }
+            } else if HttpMethod::all().iter().any(|m| m.as_str() == key) {
+                out.insert(key.clone(), self.operation(value, &path_params));
+            } else {
+                out.insert(key.clone(), convert_schema(value));
+            }
+        }
+        JsonValue::Object(out)
+    }
+
+    /// Convert one operation; `path_params` are the path item's parameters,
+    /// whose body or form entries apply to the operation unless overridden.
+    fn operation(&self, op: &JsonValue, path_params: &[&JsonValue]) -> JsonValue {
+        let Some(op) = op.as_object() else {
+            return op.clone();
+        };
+        let consumes = media_types(op.get("consumes"))
+            .or_else(|| self.consumes.clone())
+            .unwrap_or_else(default_media_types);
+        let produces = media_types(op.get("produces"))
+            .or_else(|| self.produces.clone())
+            .unwrap_or_else(default_media_types);
+
+        let op_params: Vec<&JsonValue> = op
+            .get("parameters")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // Body and form parameters, with operation-level ones overriding path-level ones
+        let mut body_params: Vec<&JsonValue> = Vec::new();
+        for param in path_params
+            .iter()
+            .copied()
+            .chain(op_params.iter().map(|p| self.resolve_parameter(p)))
+            .filter(|p| is_body_parameter(p))
+        {
+            body_params.retain(|existing| parameter_key(existing) != parameter_key(param));
+            if param.get("in").and_then(JsonValue::as_str) == Some("body") {
+                body_params.retain(|existing| {
+                    existing.get("in").and_then(JsonValue::as_str) != Some("body")
+                });
+            }
+            body_params.push(param);
+        }
+
+        let mut out = JsonMap::new();
+        for (key, value) in op {
+            match key.as_str() {
+                "consumes" | "produces" | "schemes" => {}
+                "parameters" => {
+                    let params: Vec<_> = op_params
+                        .iter()
+                        .filter(|p| !is_body_parameter(self.resolve_parameter(p)))
+                        .map(|p| convert_parameter(p))
+                        .collect();
+                    if !params.is_empty() {
+                        out.insert(key.clone(), JsonValue::Array(params));
+                    }
+                }
+                "responses" => {
+                    let responses = value
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .map(|(status, response)| {
+                            (status.clone(), convert_response(response, &produces))
+                        })
+                        .collect();
+                    out.insert(key.clone(), JsonValue::Object(responses));
+                }
+                _ => {
+                    out.insert(key.clone(), convert_schema(value));
+                }
+            }
+        }
+
+        if let Some(body) = request_body(&body_params, &consumes) {
+            out.insert("requestBody".to_string(), body);
+        }
+        JsonValue::Object(out)
+    }
+
+    /// Follow a `#/parameters/<name>` reference to the shared parameter it names
+    fn resolve_parameter<'p>(&'p self, param: &'p JsonValue) -> &'p JsonValue {
+        param
+            .get("$ref")
+            .and_then(JsonValue::as_str)
+            .and_then(|r| r.strip_prefix("#/parameters/"))
+            .and_then(|name| self.root.get("parameters")?.get(name))
+            .unwrap_or(param)
+    }
+}
+
+fn default_media_types() -> Vec<String> {
+    vec![DEFAULT_MEDIA_TYPE.to_string()]
+}
+
+/// Read a `consumes`/`produces` list; an absent or empty list yields `None`
+fn media_types(value: Option<&JsonValue>) -> Option<Vec<String>> {
+    let types: Vec<String> = value?
+        .as_array()?
+        .iter()
+        .filter_map(JsonValue::as_str)
+        .map(str::to_string)
+        .collect();
+    (!types.is_empty()).then_some(types)
+}
+
+fn is_body_parameter(param: &JsonValue) -> bool {
+    matches!(
+        param.get("in").and_then(JsonValue::as_str),
+        Some("body" | "formData")
+    )
+}
+
+fn parameter_key(param: &JsonValue) -> (Option<&str>, Option<&str>) {
+    (
+        param.get("name").and_then(JsonValue::as_str),
+        param.get("in").and_then(JsonValue::as_str),
+    )
+}
+
+/// Rewrite a reference into one of the sections that moved under `components`
+fn rewrite_ref(reference: &str) -> String {
+    const MOVED: &[(&str, &str)] = &[
+        ("#/definitions/", "#/components/schemas/"),
+        ("#/parameters/", "#/components/parameters/"),
+        ("#/responses/", "#/components/responses/"),
+    ];
+    MOVED
+        .iter()
+        .find_map(|(from, to)| {
+            reference
+                .strip_prefix(from)
+                .map(|rest| format!("{to}{rest}"))
+        })
+        .unwrap_or_else(|| reference.to_string())
+}
+
+/// Convert a Swagger schema (or any value containing schemas) to its OpenAPI 3 form
+fn convert_schema(value: &JsonValue) -> JsonValue {
+    match value {
+        JsonValue::Object(map) => {
+            let mut out = JsonMap::new();
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("$ref", JsonValue::String(reference)) => {
+                        out.insert(key.clone(), json!(rewrite_ref(reference)));
+                    }
+                    ("x-nullable", _) => {
+                        out.insert("nullable".to_string(), value.clone());
+                    }
+                    ("type", JsonValue::String(ty)) if ty == "file" => {
+                        out.insert(key.clone(), json!("string"));
+                        out.entry("format").or_insert_with(|| json!("binary"));
+                    }
+                    ("discriminator", JsonValue::String(property)) => {
+                        out.insert(key.clone(), json!({ "propertyName": property }));
+                    }
+                    _ => {
+                        out.insert(key.clone(), convert_schema(value));
+                    }
+                }
+            }
+            JsonValue::Object(out)
+        }
+        JsonValue::Array(items) => JsonValue::Array(items.iter().map(convert_schema).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Split a parameter (or header, or `items` object) into its own fields and a schema
+fn split_schema_fields(
+    value: &JsonMap<String, JsonValue>,
+) -> (JsonMap<String, JsonValue>, JsonMap<String, JsonValue>) {
+    let mut fields = JsonMap::new();
+    let mut schema = JsonMap::new();
+    for (key, value) in value {
+        if key == "items" {
+            // Nested items objects carry their own collectionFormat, which has no schema form
+            let items = value
+                .as_object()
+                .map(|items| JsonValue::Object(split_schema_fields(items).1))
+                .unwrap_or_else(|| value.clone());
+            schema.insert(key.clone(), items);
+        } else if PARAMETER_SCHEMA_KEYS.contains(&key.as_str()) {
+            schema.insert(key.clone(), value.clone());
+        } else if key != "collectionFormat" {
+            fields.insert(key.clone(), value.clone());
+        }
+    }
+    (fields, schema)
+}
+
+/// Convert a non-body parameter, moving its type information into `schema`
+fn convert_parameter(param: &JsonValue) -> JsonValue {
+    let Some(map) = param.as_object() else {
+        return param.clone();
+    };
+    if let Some(reference) = map.get("$ref").and_then(JsonValue::as_str) {
+        return json!({ "$ref": rewrite_ref(reference) });
+    }
+
+    let (mut out, schema) = split_schema_fields(map);
+    let location = map.get("in").and_then(JsonValue::as_str).unwrap_or("");
+    let is_array = map.get("type").and_then(JsonValue::as_str) == Some("array");
+    if is_array {
+        let collection_format = map
+            .get("collectionFormat")
+            .and_then(JsonValue::as_str)
+            .unwrap_or("csv");
+        let (style, explode) = match (collection_format, location) {
+            ("multi", _) => ("form", true),
+            ("ssv", "query") => ("spaceDelimited", false),
+            ("pipes", "query") => ("pipeDelimited", false),
+            (_, "query" | "cookie") => ("form", false),
+            _ => ("simple", false),
+        };
+        out.insert("style".to_string(), json!(style));
+        out.insert("explode".to_string(), json!(explode));
+    }
+    if !schema.is_empty() {
+        out.insert(
+            "schema".to_string(),
+            convert_schema(&JsonValue::Object(schema)),
+        );
+    }
+    JsonValue::Object(out)
+}
+
+/// Build a `requestBody` from an operation's body or form parameters
+fn request_body(params: &[&JsonValue], consumes: &[String]) -> Option<JsonValue> {
+    if let Some(body) = params
+        .iter()
+        .find(|p| p.get("in").and_then(JsonValue::as_str) == Some("body"))
+    {
+        let schema = convert_schema(body.get("schema").unwrap_or(&json!({})));
+        let content: JsonMap<_, _> = consumes
+            .iter()
+            .map(|media_type| (media_type.clone(), json!({ "schema": schema })))
+            .collect();
+        let mut out = JsonMap::new();
+        if let Some(description) = body.get("description") {
+            out.insert("description".to_string(), description.clone());
+        }
+        out.insert("content".to_string(), JsonValue::Object(content));
+        if let Some(required) = body.get("required") {
+            out.insert("required".to_string(), required.clone());
+        }
+        return Some(JsonValue::Object(out));
+    }
+
+    if params.is_empty() {
+        return None;
+    }
+
+    let mut properties = JsonMap::new();
+    let mut required = Vec::new();
+    let mut has_file = false;
+    for param in params {
+        let Some(map) = param.as_object() else {
+            continue;
+        };
+        let Some(name) = map.get("name").and_then(JsonValue::as_str) else {
+            continue;
+        };
+        has_file |= map.get("type").and_then(JsonValue::as_str) == Some("file");
+        let (fields, mut schema) = split_schema_fields(map);
+        if let Some(description) = fields.get("description") {
+            schema.insert("description".to_string(), description.clone());
+        }
+        if map.get("required").and_then(JsonValue::as_bool) == Some(true) {
+            required.push(json!(name));
+        }
+        properties.insert(name.to_string(), convert_schema(&JsonValue::Object(schema)));
+    }
+
+    let media_type = if has_file || consumes.iter().any(|t| t == MULTIPART_FORM) {
+        MULTIPART_FORM
+    } else {
+        FORM_URLENCODED
+    };
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = JsonValue::Array(required);
+    }
+    Some(json!({
+        "content": { media_type: { "schema": schema } },
+        "required": params.iter().any(|p| p.get("required").and_then(JsonValue::as_bool) == Some(true)),
+    }))
+}
+
+/// Convert a response, moving its `schema` under `content` for each produced media type
+fn convert_response(response: &JsonValue, produces: &[String]) -> JsonValue {
+    let Some(map) = response.as_object() else {
+        return response.clone();
+    };
+    if let Some(reference) = map.get("$ref").and_then(JsonValue::as_str) {
+        return json!({ "$ref": rewrite_ref(reference) });
+    }
+
+    let mut out = JsonMap::new();
+    // OpenAPI 3 requires a description on every response
+    out.insert(
+        "description".to_string(),
+        map.get("description").cloned().unwrap_or_else(|| json!("")),
+    );
+    let mut content = JsonMap::new();
+    if let Some(schema) = map.get("schema") {
+        let schema = convert_schema(schema);
+        for media_type in produces {
+            content.insert(media_type.clone(), json!({ "schema": schema }));
+        }
+    }
+    if let Some(examples) = map.get("examples").and_then(JsonValue::as_object) {
+        for (media_type, example) in examples {
+            let entry = content
+                .entry(media_type.clone())
+                .or_insert_with(|| json!({}));
+            entry["example"] = example.clone();
+        }
+    }
+    if !content.is_empty() {
+        out.insert("content".to_string(), JsonValue::Object(content));
+    }
+
+    for (key, value) in map {
+        match key.as_str() {
+            "description" | "schema" | "examples" => {}
+            "headers" => {
+                let headers = value
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, header)| (name.clone(), convert_header(header)))
+                    .collect();
+                out.insert(key.clone(), JsonValue::Object(headers));
+            }
+            _ => {
+                out.insert(key.clone(), convert_schema(value));
+            }
+        }
+    }
+    JsonValue::Object(out)
+}
+
+fn convert_header(header: &JsonValue) -> JsonValue {
+    let Some(map) = header.as_object() else {
+        return header.clone();
+    };
+    let (mut out, schema) = split_schema_fields(map);
+    if !schema.is_empty() {
+        out.insert(
+            "schema".to_string(),
+            convert_schema(&JsonValue::Object(schema)),
+        );
+    }
+    JsonValue::Object(out)
+}
+
+/// Convert a `securityDefinitions` entry to an OpenAPI 3 security scheme
+fn convert_security_scheme(scheme: &JsonValue) -> JsonValue {
+    let Some(map) = scheme.as_object() else {
+        return scheme.clone();
+    };
+    let mut out = JsonMap::new();
+    match map.get("type").and_then(JsonValue::as_str) {
+        Some("basic") => {
+            out.insert("type".to_string(), json!("http"));
+            out.insert("scheme".to_string(), json!("basic"));
+        }
+        Some("oauth2") => {
+            let flow_name = match map.get("flow").and_then(JsonValue::as_str) {
+                Some("password") => "password",
+                Some("application") => "clientCredentials",
+                Some("accessCode") => "authorizationCode",
+                _ => "implicit",
+            };
+            let mut flow = JsonMap::new();
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(url) = map.get(key) {
+                    flow.insert(key.to_string(), url.clone());
+                }
+            }
+            flow.insert(
+                "scopes".to_string(),
+                map.get("scopes").cloned().unwrap_or_else(|| json!({})),
+            );
+            out.insert("type".to_string(), json!("oauth2"));
+            out.insert("flows".to_string(), json!({ flow_name: flow }));
+        }
+        _ => {
+            return scheme.clone();
+        }
+    }
+    for (key, value) in map {
+        if key == "description" || key.starts_with("x-") {
+            out.insert(key.clone(), value.clone());
+        }
+    }
+    JsonValue::Object(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swagger(extra: JsonValue) -> JsonValue {
+        let mut doc = json!({
+            "swagger": "2.0",
+            "info": { "title": "Legacy", "version": "1.0" },
+            "paths": {}
+        });
+        for (key, value) in extra.as_object().unwrap() {
+            doc[key] = value.clone();
+        }
+        doc
+    }
+
+    #[test]
+    fn test_upgrade_leaves_openapi_3_untouched() {
+        let doc = json!({ "openapi": "3.0.0", "paths": {}, "host": "example.com" });
+        assert_eq!(upgrade(doc.clone()), doc);
+    }
+
+    #[test]
+    fn test_upgrade_copies_non_schema_keys_unchanged() {
+        let extra = json!({
+            "info": { "title": "Legacy", "version": "1.0", "x-nullable": true },
+            "tags": [{ "name": "files", "type": "file" }],
+            "x-vendor": { "discriminator": "kind", "$ref": "#/definitions/Pet" }
+        });
+        let doc = upgrade(swagger(extra.clone()));
+        for key in ["info", "tags", "x-vendor"] {
+            assert_eq!(doc[key], extra[key], "{key}");
+        }
+    }
+
+    #[test]
+    fn test_upgrade_builds_servers_preferring_https() {
+        let doc = upgrade(swagger(json!({
+            "host": "api.example.com",
+            "basePath": "/v1",
+            "schemes": ["http", "https"]
+        })));
+
+        assert_eq!(doc["openapi"], "3.0.3");
+        assert!(doc.get("swagger").is_none());
+        assert_eq!(
+            doc["servers"],
+            json!([
+                { "url": "https://api.example.com/v1" },
+                { "url": "http://api.example.com/v1" }
+            ])
+        );
+
+        let relative = upgrade(swagger(json!({ "basePath": "/api" })));
+        assert_eq!(relative["servers"], json!([{ "url": "/api" }]));
+    }
+
+    #[test]
+    fn test_upgrade_moves_definitions_and_rewrites_refs() {
+        let doc = upgrade(swagger(json!({
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "discriminator": "kind",
+                    "properties": {
+                        "owner": { "$ref": "#/definitions/Owner" },
+                        "nickname": { "type": "string", "x-nullable": true }
+                    }
+                },
+                "Owner": { "type": "object" }
+            },
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "produces": ["application/json", "application/xml"],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "schema": { "type": "array", "items": { "$ref": "#/definitions/Pet" } },
+                                "headers": { "X-Rate-Limit": { "type": "integer", "format": "int32" } }
+                            },
+                            "404": { "$ref": "#/responses/NotFound" }
+                        }
+                    }
+                }
+            },
+            "responses": { "NotFound": { "description": "missing" } }
+        })));
+
+        let pet = &doc["components"]["schemas"]["Pet"];
+        assert_eq!(
+            pet["properties"]["owner"]["$ref"],
+            "#/components/schemas/Owner"
+        );
+        assert_eq!(pet["properties"]["nickname"]["nullable"], true);
+        assert_eq!(pet["discriminator"], json!({ "propertyName": "kind" }));
+
+        let responses = &doc["paths"]["/pets"]["get"]["responses"];
+        let ok = &responses["200"];
+        assert_eq!(
+            ok["content"]["application/xml"]["schema"]["items"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+        assert_eq!(
+            ok["headers"]["X-Rate-Limit"]["schema"],
+            json!({ "type": "integer", "format": "int32" })
+        );
+        assert_eq!(responses["404"]["$ref"], "#/components/responses/NotFound");
+        assert_eq!(
+            doc["components"]["responses"]["NotFound"]["description"],
+            "missing"
+        );
+        assert!(doc["paths"]["/pets"]["get"].get("produces").is_none());
+    }
+
+    #[test]
+    fn test_upgrade_converts_body_parameter_to_request_body() {
+        let doc = upgrade(swagger(json!({
+            "consumes": ["application/json"],
+            "parameters": {
+                "PetBody": {
+                    "name": "pet",
+                    "in": "body",
+                    "required": true,
+                    "schema": { "$ref": "#/definitions/Pet" }
+                },
+                "Limit": { "name": "limit", "in": "query", "type": "integer" }
+            },
+            "paths": {
+                "/pets": {
+                    "post": {
+                        "consumes": ["application/json", "application/xml"],
+                        "parameters": [
+                            { "$ref": "#/parameters/PetBody" },
+                            { "$ref": "#/parameters/Limit" }
+                        ],
+                        "responses": { "201": { "description": "created" } }
+                    }
+                }
+            }
+        })));
+
+        let op = &doc["paths"]["/pets"]["post"];
+        assert_eq!(
+            op["parameters"],
+            json!([{ "$ref": "#/components/parameters/Limit" }])
+        );
+        assert_eq!(op["requestBody"]["required"], true);
+        for media_type in ["application/json", "application/xml"] {
+            assert_eq!(
+                op["requestBody"]["content"][media_type]["schema"]["$ref"],
+                "#/components/schemas/Pet"
+            );
+        }
+        let components = &doc["components"]["parameters"];
+        assert!(components.get("PetBody").is_none());
+        assert_eq!(components["Limit"]["schema"], json!({ "type": "integer" }));
+    }
+
+    #[test]
+    fn test_upgrade_converts_form_parameters() {
+        let doc = upgrade(swagger(json!({
+            "paths": {
+                "/pets/{id}/image": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "type": "integer", "format": "int64" }
+                    ],
+                    "post": {
+                        "consumes": ["multipart/form-data"],
+                        "parameters": [
+                            { "name": "note", "in": "formData", "type": "string", "description": "Caption" },
+                            { "name": "file", "in": "formData", "type": "file", "required": true }
+                        ],
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                },
+                "/login": {
+                    "post": {
+                        "parameters": [
+                            { "name": "user", "in": "formData", "type": "string", "required": true }
+                        ],
+                        "responses": { "200": { "description": "ok" } }
+                    }
+                }
+            }
+        })));
+
+        let item = &doc["paths"]["/pets/{id}/image"];
+        assert_eq!(
+            item["parameters"][0]["schema"],
+            json!({ "type": "integer", "format": "int64" })
+        );
+        let upload = &item["post"];
+        assert!(upload.get("parameters").is_none());
+        let schema = &upload["requestBody"]["content"]["multipart/form-data"]["schema"];
+        assert_eq!(
+            schema["properties"]["file"],
+            json!({ "type": "string", "format": "binary" })
+        );
+        assert_eq!(
+            schema["properties"]["note"],
+            json!({ "type": "string", "description": "Caption" })
+        );
+        assert_eq!(schema["required"], json!(["file"]));
+
+        let login = &doc["paths"]["/login"]["post"]["requestBody"];
+        assert!(login["content"][FORM_URLENCODED]["schema"]["properties"]["user"].is_object());
+        assert_eq!(login["required"], true);
+    }
+
+    #[test]
+    fn test_upgrade_maps_collection_formats() {
+        let doc = upgrade(swagger(json!({
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "parameters": [
+                            { "name": "tags", "in": "query", "type": "array", "items": { "type": "string" } },
+                            { "name": "ids", "in": "query", "type": "array", "collectionFormat": "multi", "items": { "type": "integer" } },
+                            { "name": "kinds", "in": "query", "type": "array", "collectionFormat": "pipes", "items": { "type": "string" } },
+                            { "name": "X-Ids", "in": "header", "type": "array", "items": { "type": "string", "collectionFormat": "csv" } }
+                        ],
+                        "responses": {}
+                    }
+                }
+            }
+        })));
+
+        let params = doc["paths"]["/pets"]["get"]["parameters"]
+            .as_array()
+            .unwrap();
+        let style = |i: usize| (params[i]["style"].clone(), params[i]["explode"].clone());
+        assert_eq!(style(0), (json!("form"), json!(false)));
+        assert_eq!(style(1), (json!("form"), json!(true)));
+        assert_eq!(style(2), (json!("pipeDelimited"), json!(false)));
+        assert_eq!(style(3), (json!("simple"), json!(false)));
+        assert_eq!(
+            params[3]["schema"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert!(params[0].get("collectionFormat").is_none());
+    }
+
+    #[test]
+    fn test_upgrade_converts_security_definitions() {
+        let doc = upgrade(swagger(json!({
+            "securityDefinitions": {
+                "basic": { "type": "basic", "description": "HTTP basic" },
+                "key": { "type": "apiKey", "name": "api_key", "in": "header" },
+                "oauth": {
+                    "type": "oauth2",
+                    "flow": "accessCode",
+                    "authorizationUrl": "https://auth.example.com/authorize",
+                    "tokenUrl": "https://auth.example.com/token",
+                    "scopes": { "read": "Read access" }
+                }
+            }
+        })));
+
+        let schemes = &doc["components"]["securitySchemes"];
+        assert_eq!(
+            schemes["basic"],
+            json!({ "type": "http", "scheme": "basic", "description": "HTTP basic" })
+        );
+        assert_eq!(
+            schemes["key"],
+            json!({ "type": "apiKey", "name": "api_key", "in": "header" })
+        );
+        assert_eq!(
+            schemes["oauth"]["flows"]["authorizationCode"],
+            json!({
+                "authorizationUrl": "https://auth.example.com/authorize",
+                "tokenUrl": "https://auth.example.com/token",
+                "scopes": { "read": "Read access" }
+            })
+        );
+    }
+}
@@ -233,14 +233,13 @@ impl TemplateDir {
         let mut locations = Vec::new();
 
         // Check executable directory and parent directories
-        if let Ok(exe_path) = std::env::current_exe() {
-            if let Some(exe_dir) = exe_path.parent() {
-                if let Ok(exe_dir_abs) = exe_dir.canonicalize() {
-                    locations.push(exe_dir_abs.clone());
-                    if let Some(parent_dir) = exe_dir_abs.parent() {
-                        locations.push(parent_dir.to_path_buf());
-                    }
-                }
+        if let Ok(exe_path) = std::env::current_exe()
+            && let Some(exe_dir) = exe_path.parent()
+            && let Ok(exe_dir_abs) = exe_dir.canonicalize()
+        {
+            locations.push(exe_dir_abs.clone());
+            if let Some(parent_dir) = exe_dir_abs.parent() {
+                locations.push(parent_dir.to_path_buf());
             }
         }
 
@@ -301,25 +300,23 @@ impl TemplateDir {
     /// Check if a path is under any of the allowed locations
     fn is_path_allowed(canonical_path: &Path) -> bool {
         // After security checks pass, allow paths under user's home directory
-        if let Some(home_dir) = dirs::home_dir() {
-            if let Ok(home_canonical) = home_dir.canonicalize() {
-                if canonical_path.starts_with(&home_canonical) {
-                    debug!(
-                        "Template path allowed under home directory: {}",
-                        canonical_path.display()
-                    );
-                    return true;
-                }
-            }
+        if let Some(home_dir) = dirs::home_dir()
+            && let Ok(home_canonical) = home_dir.canonicalize()
+            && canonical_path.starts_with(&home_canonical)
+        {
+            debug!(
+                "Template path allowed under home directory: {}",
+                canonical_path.display()
+            );
+            return true;
         }
 
         // Allow paths under current working directory and its parents (for development)
-        if let Ok(current_dir) = std::env::current_dir() {
-            if let Ok(current_canonical) = current_dir.canonicalize() {
-                if Self::is_under_workspace(canonical_path, &current_canonical) {
-                    return true;
-                }
-            }
+        if let Ok(current_dir) = std::env::current_dir()
+            && let Ok(current_canonical) = current_dir.canonicalize()
+            && Self::is_under_workspace(canonical_path, &current_canonical)
+        {
+            return true;
         }
 
         // Allow paths under CARGO_MANIFEST_DIR and its parents (for development/testing)
@@ -334,14 +331,14 @@ impl TemplateDir {
                     return true;
                 }
 
-                if let Some(parent) = manifest_canonical.parent() {
-                    if canonical_path.starts_with(parent) {
-                        debug!(
-                            "Template path allowed under cargo workspace: {}",
-                            canonical_path.display()
-                        );
-                        return true;
-                    }
+                if let Some(parent) = manifest_canonical.parent()
+                    && canonical_path.starts_with(parent)
+                {
+                    debug!(
+                        "Template path allowed under cargo workspace: {}",
+                        canonical_path.display()
+                    );
+                    return true;
                 }
             }
         }
@@ -464,15 +461,16 @@ impl TemplateDir {
         }
 
         // Handle macOS /private/ prefixed system directories
-        if second == "private" && components.len() >= 3 {
-            if let Some(std::path::Component::Normal(third)) = components.get(2) {
-                let third_str = third.to_str().unwrap_or("");
-                if Self::is_system_directory(third_str) {
-                    if Self::is_temp_exception(&components, 2) {
-                        return Ok(()); // Allow /private/tmp and /private/var/tmp
-                    }
-                    return Err(Self::system_directory_error(canonical_path));
+        if second == "private"
+            && components.len() >= 3
+            && let Some(std::path::Component::Normal(third)) = components.get(2)
+        {
+            let third_str = third.to_str().unwrap_or("");
+            if Self::is_system_directory(third_str) {
+                if Self::is_temp_exception(&components, 2) {
+                    return Ok(()); // Allow /private/tmp and /private/var/tmp
                 }
+                return Err(Self::system_directory_error(canonical_path));
             }
         }
 
@@ -495,10 +493,10 @@ impl TemplateDir {
             }
 
             // Allow /var/tmp or /private/var/tmp
-            if dir_str == "var" {
-                if let Some(std::path::Component::Normal(subdir)) = components.get(base_index + 1) {
-                    return subdir.to_str().unwrap_or("") == "tmp";
-                }
+            if dir_str == "var"
+                && let Some(std::path::Component::Normal(subdir)) = components.get(base_index + 1)
+            {
+                return subdir.to_str().unwrap_or("") == "tmp";
             }
         }
         false
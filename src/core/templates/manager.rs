@@ -2,16 +2,21 @@
 
 // Internal imports (std, crate)
 use std::{
+    collections::BTreeMap,
     io,
     path::{Path, PathBuf},
     sync::Arc,
 };
 
 use crate::core::{
+    auth::collect_auth_schemes,
     config::Config,
     error::Result,
-    openapi::{OpenApiContext, OpenApiOperation},
+    events::collect_events,
+    openapi::{OpenApiContext, OpenApiOperation, resolve_server_url},
     protocol::Protocol,
+    selection::OperationSelection,
+    tool_names::assign_tool_names,
     utils::to_snake_case,
 };
 use crate::mcp::builders::EndpointContext;
@@ -25,7 +30,7 @@ use super::{
 use serde::Serialize;
 use serde_json::{Map, Value as JsonValue, json};
 use tera::{Context, Tera};
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 /// Manages loading and rendering of code generation templates
 #[derive(Debug, Clone)]
@@ -357,7 +362,6 @@ impl TemplateManager {
                             &tera_context,
                             output_dir,
                             &operations,
-                            &template_opts,
                             spec,
                         )
                         .await?;
@@ -493,6 +497,12 @@ impl TemplateManager {
         // Add project name from config (user-specified)
         base_map.insert("project_name".to_string(), json!(config.project_name));
 
+        // Add the URI scheme of the resources the API data is exposed as
+        base_map.insert(
+            "resource_scheme".to_string(),
+            json!(resource_scheme(&config.project_name)),
+        );
+
         // Add protocol information
         base_map.insert("protocol".to_string(), json!(self.protocol().name()));
 
@@ -524,11 +534,64 @@ impl TemplateManager {
             base_map.insert("spec".to_string(), spec_value);
         }
 
+        // Add the typed security schemes, reporting any that could not be parsed
+        let (security_schemes, diagnostics) = openapi_context.security_schemes();
+        for diagnostic in &diagnostics {
+            warn!("{diagnostic}");
+        }
+
+        // Add the schemes generated servers can authenticate to the API with
+        let (auth_schemes, diagnostics) = collect_auth_schemes(&security_schemes);
+        for diagnostic in &diagnostics {
+            warn!("{diagnostic}");
+        }
+        base_map.insert("security_schemes".to_string(), json!(security_schemes));
+        base_map.insert("auth_schemes".to_string(), json!(auth_schemes));
+
         // Add spec file name for reference in templates
         base_map.insert("spec_file_name".to_string(), json!("openapi.json"));
 
         // Extract operations from the OpenAPI spec
-        let operations = openapi_context.parse_operations().await?;
+        let mut operations = openapi_context.parse_operations().await?;
+
+        // Operations hidden by their `x-mcp` extension get no tool at all
+        operations.retain(|op| !op.mcp.hidden);
+
+        // Name the tools of all operations, so that names do not depend on the selection
+        let renames = assign_tool_names(&mut operations, &config.tool_naming)?;
+
+        // Keep the selected operations; every template sees the same ones
+        let selection = OperationSelection::new(config)?;
+        let available = operations.len();
+        operations.retain(|op| {
+            selection.selects(op)
+                && template_opts
+                    .as_ref()
+                    .is_none_or(|opts| opts.selects(&op.id))
+        });
+        if operations.is_empty() && available > 0 {
+            return Err(crate::core::Error::config(format!(
+                "None of the {available} operations match the include/exclude filters"
+            )));
+        }
+        debug!("Selected {} of {available} operations", operations.len());
+
+        // Explain every rename made to keep the selected tool names valid and unique
+        for diagnostic in &renames {
+            if operations
+                .iter()
+                .any(|op| op.pointer() == diagnostic.pointer)
+            {
+                warn!("{diagnostic}");
+            }
+        }
+
+        // Add the webhooks of the spec and the callbacks of the selected operations
+        let (events, diagnostics) = collect_events(openapi_context, &operations);
+        for diagnostic in &diagnostics {
+            warn!("{diagnostic}");
+        }
+        base_map.insert("events".to_string(), json!(events));
 
         // Transform endpoints using language-specific builder
         let endpoints =
@@ -550,33 +613,10 @@ impl TemplateManager {
             }
         }
 
-        // Add base API URL from OpenAPI spec and user-provided base URL
-        if let Some(spec_url) = openapi_context.base_path() {
-            let final_url = if spec_url.starts_with("http://") || spec_url.starts_with("https://") {
-                // Spec contains a fully qualified URL, use it directly
-                spec_url
-            } else if spec_url.starts_with("/") {
-                // Spec contains a relative path, combine with user-provided base URL
-                if let Some(base_url) = &config.base_url {
-                    let base_str = base_url.to_string();
-                    let trimmed = base_str.trim_end_matches('/');
-                    format!("{trimmed}{spec_url}")
-                } else {
-                    return Err(crate::core::Error::Template(format!(
-                        "OpenAPI spec contains a relative server URL '{spec_url}', but no --base-url was provided. Please provide a base URL (e.g., --base-url https://api.example.com)"
-                    )));
-                }
-            } else {
-                return Err(crate::core::Error::Template(format!(
-                    "Invalid server URL format in OpenAPI spec: '{spec_url}'. URL must be either a fully qualified URL (https://api.example.com/v1) or a relative path (/api/v1)"
-                )));
-            };
-            base_map.insert("base_api_url".to_string(), json!(final_url));
-        } else {
-            return Err(crate::core::Error::Template(
-                "No server URL found in OpenAPI spec. Please define at least one server in the 'servers' section (OpenAPI 3.0+) or 'host' field (Swagger 2.0) of your OpenAPI specification".to_string()
-            ));
-        }
+        // Add the selected server URL plus every server the generated code can switch to
+        let (base_api_url, servers) = select_server(openapi_context, config)?;
+        base_map.insert("base_api_url".to_string(), json!(base_api_url));
+        base_map.insert("servers".to_string(), json!(servers));
 
         // For debugging, log the context keys
         let keys_str: Vec<String> = base_map.keys().map(|k| k.to_string()).collect();
@@ -599,13 +639,13 @@ impl TemplateManager {
         );
 
         // Create the output directory if it doesn't exist
-        if let Some(parent) = output_path.parent() {
-            if !parent.exists() {
-                log::debug!("Creating parent directory: {}", parent.display());
-                tokio::fs::create_dir_all(parent).await.map_err(|e| {
-                    io::Error::other(format!("Failed to create output directory: {e}"))
-                })?;
-            }
+        if let Some(parent) = output_path.parent()
+            && !parent.exists()
+        {
+            log::debug!("Creating parent directory: {}", parent.display());
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| io::Error::other(format!("Failed to create output directory: {e}")))?;
         }
 
         // Create the file context
@@ -706,7 +746,6 @@ impl TemplateManager {
         base_context: &Context,
         output_path: &Path,
         operations: &[OpenApiOperation],
-        template_opts: &Option<TemplateOptions>,
         spec: &OpenApiContext,
     ) -> Result<()> {
         // Create schemas directory
@@ -717,281 +756,262 @@ impl TemplateManager {
 
         for operation in operations {
             // Language-specific fields like fn_name must be injected by a builder; OpenApiOperation is language-agnostic.
-            let include = template_opts
-                .as_ref()
-                .map(|opts| {
-                    opts.all_operations
-                        || opts.include_operations.is_empty()
-                        || opts.include_operations.contains(&operation.id)
-                })
-                .unwrap_or(true);
-            let exclude = template_opts
-                .as_ref()
-                .map(|opts| opts.exclude_operations.contains(&operation.id))
-                .unwrap_or(false);
-
-            if include && !exclude {
-                let mut context = base_context.clone();
+            let mut context = base_context.clone();
 
-                let builder = EndpointContext::get_builder(self.template_kind());
-                let endpoint_context = builder.build(operation)?;
+            let builder = EndpointContext::get_builder(self.template_kind());
+            let endpoint_context = builder.build(operation)?;
 
-                // Merge the endpoint context into the template context
-                if let Some(obj) = endpoint_context.as_object() {
-                    for (key, value) in obj {
-                        context.insert(key, &value);
-                    }
+            // Merge the endpoint context into the template context
+            if let Some(obj) = endpoint_context.as_object() {
+                for (key, value) in obj {
+                    context.insert(key, &value);
                 }
+            }
 
-                // Add operation metadata
-                context.insert("operation_id", &operation.id);
-                context.insert("method", &operation.method);
-                context.insert("path", &operation.path);
-
-                // Insert OpenAPI-native fields
-                context.insert("operation_id", &operation.id);
-
-                // Sanitize and add text fields
-                let sanitized_summary = operation.summary.as_deref().map(|s| {
-                    s.chars()
-                        .filter(|c| c.is_ascii_alphanumeric() || c.is_whitespace())
-                        .collect::<String>()
-                        .trim()
-                        .to_string()
-                });
-
-                let sanitized_description = operation.description.as_deref().map(|s| {
-                    s.chars()
-                        .filter(|c| {
-                            c.is_ascii_alphanumeric() || c.is_whitespace() || *c == '.' || *c == ','
-                        })
-                        .collect::<String>()
-                        .trim()
-                        .to_string()
-                });
-
-                context.insert("summary", &sanitized_summary);
-                context.insert("description", &sanitized_description);
-                context.insert("deprecated", &operation.deprecated);
-
-                // Add tags with proper sanitization
-                let sanitized_tags: Vec<String> = operation
-                    .tags
-                    .as_ref()
-                    .map(|tags| {
-                        tags.iter()
-                            .map(|t| t.trim().replace("\n", " ").replace("\r", " "))
-                            .collect()
+            // Add operation metadata
+            context.insert("operation_id", &operation.id);
+            context.insert("method", &operation.method);
+            context.insert("path", &operation.path);
+
+            // Insert OpenAPI-native fields
+            context.insert("operation_id", &operation.id);
+
+            // Sanitize and add text fields
+            let sanitized_summary = operation.summary.as_deref().map(|s| {
+                s.chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || c.is_whitespace())
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            });
+
+            let sanitized_description = operation.description.as_deref().map(|s| {
+                s.chars()
+                    .filter(|c| {
+                        c.is_ascii_alphanumeric() || c.is_whitespace() || *c == '.' || *c == ','
                     })
-                    .unwrap_or_default();
-                context.insert("tags", &sanitized_tags);
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            });
+
+            context.insert("summary", &sanitized_summary);
+            context.insert("description", &sanitized_description);
+            context.insert("deprecated", &operation.deprecated);
+
+            // Add tags with proper sanitization
+            let sanitized_tags: Vec<String> = operation
+                .tags
+                .as_ref()
+                .map(|tags| {
+                    tags.iter()
+                        .map(|t| t.trim().replace("\n", " ").replace("\r", " "))
+                        .collect()
+                })
+                .unwrap_or_default();
+            context.insert("tags", &sanitized_tags);
 
-                // Extract and process parameters with proper error handling
-                let parameter_info: Vec<serde_json::Value> = operation
-                    .parameters
-                    .as_ref()
-                    .map(|params| {
-                        params
-                            .iter()
-                            .map(|p| {
-                                let mut param_obj = serde_json::Map::new();
-
-                                // Required fields
-                                param_obj.insert("name".to_string(), json!(&p.name));
-                                param_obj.insert("in".to_string(), json!(&p.in_));
-
-                                // Optional fields with their correct names
-                                if let Some(desc) = &p.description {
-                                    param_obj.insert("description".to_string(), json!(desc));
-                                }
+            // Extract and process parameters with proper error handling
+            let parameter_info: Vec<serde_json::Value> = operation
+                .parameters
+                .as_ref()
+                .map(|params| {
+                    params
+                        .iter()
+                        .map(|p| {
+                            let mut param_obj = serde_json::Map::new();
+
+                            // Required fields
+                            param_obj.insert("name".to_string(), json!(&p.name));
+                            param_obj.insert("in".to_string(), json!(&p.in_));
+
+                            // Optional fields with their correct names
+                            if let Some(desc) = &p.description {
+                                param_obj.insert("description".to_string(), json!(desc));
+                            }
 
-                                // Handle required field with path parameter default
-                                let is_required = p.required.unwrap_or_else(|| p.in_ == "path");
-                                param_obj.insert("required".to_string(), json!(is_required));
+                            // Handle required field with path parameter default
+                            let is_required = p.required.unwrap_or_else(|| p.in_ == "path");
+                            param_obj.insert("required".to_string(), json!(is_required));
 
-                                // Add schema if available
-                                if let Some(schema) = &p.schema {
-                                    param_obj.insert("schema".to_string(), schema.clone());
-                                }
+                            // Add schema if available
+                            if let Some(schema) = &p.schema {
+                                param_obj.insert("schema".to_string(), schema.clone());
+                            }
 
-                                // Add content if available (for complex parameters)
-                                if let Some(content) = &p.content {
-                                    param_obj.insert("content".to_string(), json!(content));
-                                }
+                            // Add content if available (for complex parameters)
+                            if let Some(content) = &p.content {
+                                param_obj.insert("content".to_string(), json!(content));
+                            }
 
-                                // Add examples if available
-                                if let Some(examples) = &p.examples {
-                                    param_obj.insert("examples".to_string(), json!(examples));
-                                }
+                            // Add examples if available
+                            if let Some(examples) = &p.examples {
+                                param_obj.insert("examples".to_string(), json!(examples));
+                            }
 
-                                // Add other optional fields
-                                if let Some(deprecated) = p.deprecated {
-                                    param_obj.insert("deprecated".to_string(), json!(deprecated));
-                                }
+                            // Add other optional fields
+                            if let Some(deprecated) = p.deprecated {
+                                param_obj.insert("deprecated".to_string(), json!(deprecated));
+                            }
 
-                                if let Some(style) = &p.style {
-                                    param_obj.insert("style".to_string(), json!(style));
-                                }
+                            if let Some(style) = &p.style {
+                                param_obj.insert("style".to_string(), json!(style));
+                            }
 
-                                if let Some(explode) = p.explode {
-                                    param_obj.insert("explode".to_string(), json!(explode));
-                                }
+                            if let Some(explode) = p.explode {
+                                param_obj.insert("explode".to_string(), json!(explode));
+                            }
 
-                                // Add allow_empty_value with correct serialization name
-                                if let Some(allow_empty) = p.allow_empty_value {
-                                    param_obj
-                                        .insert("allowEmptyValue".to_string(), json!(allow_empty));
-                                }
+                            // Add allow_empty_value with correct serialization name
+                            if let Some(allow_empty) = p.allow_empty_value {
+                                param_obj.insert("allowEmptyValue".to_string(), json!(allow_empty));
+                            }
 
-                                // Add allow_reserved with correct serialization name
-                                if let Some(allow_reserved) = p.allow_reserved {
-                                    param_obj
-                                        .insert("allowReserved".to_string(), json!(allow_reserved));
-                                }
+                            // Add allow_reserved with correct serialization name
+                            if let Some(allow_reserved) = p.allow_reserved {
+                                param_obj
+                                    .insert("allowReserved".to_string(), json!(allow_reserved));
+                            }
 
-                                // Add any vendor extensions
-                                if !p.vendor_extensions.is_empty() {
-                                    for (key, value) in &p.vendor_extensions {
-                                        if key.starts_with("x-") {
-                                            param_obj.insert(key.clone(), value.clone());
-                                        }
+                            // Add any vendor extensions
+                            if !p.vendor_extensions.is_empty() {
+                                for (key, value) in &p.vendor_extensions {
+                                    if key.starts_with("x-") {
+                                        param_obj.insert(key.clone(), value.clone());
                                     }
                                 }
+                            }
 
-                                json!(param_obj)
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
+                            json!(param_obj)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
 
-                // Add parameters to context
+            // Keep the builder's typed parameters; raw ones remain available as parameter_info
+            if !context.contains_key("parameters") {
                 context.insert(
                     "parameters",
                     &operation.parameters.clone().unwrap_or_default(),
                 );
-                context.insert("parameter_info", &parameter_info);
+            }
+            context.insert("parameter_info", &parameter_info);
 
-                // Process responses
-                context.insert("responses", &operation.responses);
+            // Process responses
+            context.insert("responses", &operation.responses);
 
-                // Add request body if present with sanitized properties
-                if let Some(request_body) = &operation.request_body {
-                    context.insert("has_request_body", &true);
-                    context.insert("request_body", request_body);
+            // Add request body if present with sanitized properties
+            if let Some(request_body) = &operation.request_body {
+                context.insert("has_request_body", &true);
+                context.insert("request_body", request_body);
 
-                    // Use the operation's method to extract request body properties
-                    match spec.extract_request_body_properties(operation) {
-                        Ok((props, _)) if !props.is_null() => {
-                            let property_info = OpenApiContext::extract_property_info(&props);
+                // Use the operation's method to extract request body properties
+                match spec.extract_request_body_properties(operation) {
+                    Ok((props, _)) if !props.is_null() => {
+                        let property_info = OpenApiContext::extract_property_info(&props);
+                        context.insert("request_properties", &property_info);
+                    }
+                    _ => {
+                        // Fallback to basic property extraction if the above fails
+                        if let Some(schema) = request_body
+                            .content
+                            .values()
+                            .find_map(|media_type| media_type.schema.as_ref())
+                        {
+                            let property_info = OpenApiContext::extract_property_info(schema);
                             context.insert("request_properties", &property_info);
                         }
-                        _ => {
-                            // Fallback to basic property extraction if the above fails
-                            if let Some(content) = request_body
-                                .get("content")
-                                .and_then(serde_json::Value::as_object)
-                            {
-                                for (_content_type, media_type) in content {
-                                    if let Some(schema) = media_type.get("schema") {
-                                        let property_info =
-                                            OpenApiContext::extract_property_info(schema);
-                                        context.insert("request_properties", &property_info);
-                                        break;
-                                    }
-                                }
-                            }
-                        }
                     }
-                } else {
-                    context.insert("has_request_body", &false);
-                }
-
-                // Add security requirements if present
-                if let Some(security) = &operation.security {
-                    context.insert("security", security);
                 }
+            } else {
+                context.insert("has_request_body", &false);
+            }
 
-                // Add sanitized names for use in generated code
-                let sanitized_operation_name = operation
-                    .id
-                    .chars()
-                    .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
-                    .collect::<String>();
-                context.insert("sanitized_operation_name", &sanitized_operation_name);
-
-                let endpoint_fs = if let Some(endpoint_val) = endpoint_context.get("endpoint_fs") {
-                    endpoint_val.as_str().unwrap_or(&operation.id)
-                } else {
-                    &operation.id
-                };
+            // Add security requirements if present
+            if let Some(security) = &operation.security {
+                context.insert("security", security);
+            }
 
-                let endpoint_name = if let Some(endpoint_val) = endpoint_context.get("endpoint") {
-                    endpoint_val.as_str().unwrap_or(&operation.id)
-                } else {
-                    &operation.id
-                };
+            // Add sanitized names for use in generated code
+            let sanitized_operation_name = operation
+                .id
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect::<String>();
+            context.insert("sanitized_operation_name", &sanitized_operation_name);
 
-                let sanitized_filename = to_snake_case(endpoint_fs);
-                context.insert("sanitized_filename", &sanitized_filename);
+            let endpoint_fs = if let Some(endpoint_val) = endpoint_context.get("endpoint_fs") {
+                endpoint_val.as_str().unwrap_or(&operation.tool_name)
+            } else {
+                &operation.tool_name
+            };
 
-                log::debug!("Processing template for operation: {}", operation.id);
+            let endpoint_name = if let Some(endpoint_val) = endpoint_context.get("endpoint") {
+                endpoint_val.as_str().unwrap_or(&operation.tool_name)
+            } else {
+                &operation.tool_name
+            };
 
-                // Generate schema file with proper schema extraction
-                // Use snake_case for the filename to match MCP conventions
-                let schema_filename = to_snake_case(&operation.id);
-                let schema_path = schemas_dir.join(format!("{schema_filename}.json"));
-                let mut schema_value = serde_json::to_value(operation)?;
+            let sanitized_filename = to_snake_case(endpoint_fs);
+            context.insert("sanitized_filename", &sanitized_filename);
 
-                // Dereference all $ref in the schema
-                Self::dereference_schema_refs(&mut schema_value, spec)?;
+            log::debug!("Processing template for operation: {}", operation.id);
 
-                // Remove null values from the schema
-                schema_value
-                    .as_object_mut()
-                    .unwrap()
-                    .retain(|_, v| v != &json!(null));
+            // Generate schema file with proper schema extraction
+            // Name it like the endpoint's file so templates can find it
+            let schema_filename = endpoint_fs;
+            let schema_path = schemas_dir.join(format!("{schema_filename}.json"));
+            let mut schema_value = serde_json::to_value(operation)?;
 
-                let schema_json = serde_json::to_string_pretty(&schema_value)?;
-                tokio::fs::write(&schema_path, schema_json)
-                    .await
-                    .map_err(|e| {
-                        io::Error::other(format!(
-                            "Failed to write schema file {}: {}",
-                            schema_path.display(),
-                            e
-                        ))
-                    })?;
+            // Dereference all $ref in the schema
+            Self::dereference_schema_refs(&mut schema_value, spec)?;
 
-                // Generate the output path with sanitized operation_id
-                let output_file = file
-                    .destination
-                    .replace("{{operation_id}}", endpoint_fs)
-                    .replace("{operation_id}", endpoint_fs)
-                    .replace("{{endpoint}}", endpoint_name)
-                    .replace("{endpoint}", endpoint_name);
-                let output_path = output_path.join(&output_file);
-
-                // Create parent directories if they don't exist
-                if let Some(parent) = output_path.parent() {
-                    tokio::fs::create_dir_all(parent).await?;
-                }
+            // Remove null values from the schema
+            schema_value
+                .as_object_mut()
+                .unwrap()
+                .retain(|_, v| v != &json!(null));
 
-                // Render the template
-                let rendered = self.tera.render(&file.source, &context).map_err(|e| {
-                    io::Error::other(format!("Failed to render template {}: {}", file.source, e))
+            let schema_json = serde_json::to_string_pretty(&schema_value)?;
+            tokio::fs::write(&schema_path, schema_json)
+                .await
+                .map_err(|e| {
+                    io::Error::other(format!(
+                        "Failed to write schema file {}: {}",
+                        schema_path.display(),
+                        e
+                    ))
                 })?;
 
-                // Write the file
-                tokio::fs::write(&output_path, rendered)
-                    .await
-                    .map_err(|e| {
-                        io::Error::other(format!(
-                            "Failed to write file {}: {}",
-                            output_path.display(),
-                            e
-                        ))
-                    })?;
+            // Generate the output path with sanitized operation_id
+            let output_file = file
+                .destination
+                .replace("{{operation_id}}", endpoint_fs)
+                .replace("{operation_id}", endpoint_fs)
+                .replace("{{endpoint}}", endpoint_name)
+                .replace("{endpoint}", endpoint_name);
+            let output_path = output_path.join(&output_file);
+
+            // Create parent directories if they don't exist
+            if let Some(parent) = output_path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
             }
+
+            // Render the template
+            let rendered = self.tera.render(&file.source, &context).map_err(|e| {
+                io::Error::other(format!("Failed to render template {}: {}", file.source, e))
+            })?;
+
+            // Write the file
+            tokio::fs::write(&output_path, rendered)
+                .await
+                .map_err(|e| {
+                    io::Error::other(format!(
+                        "Failed to write file {}: {}",
+                        output_path.display(),
+                        e
+                    ))
+                })?;
         }
         Ok(())
     }
@@ -1078,46 +1098,178 @@ impl TemplateManager {
         Ok(serde_json::Value::Object(context))
     }
 
-    /// Dereference all $ref in a JSON value by replacing them with actual schema definitions
+    /// Dereference all $ref in a JSON value by replacing them with actual schema definitions.
+    ///
+    /// Recursive schemas are expanded once; deeper occurrences point back at a
+    /// `$defs` entry collected at the root of `value`.
     fn dereference_schema_refs(value: &mut serde_json::Value, spec: &OpenApiContext) -> Result<()> {
+        let (mut inlined, mut defs) = spec.inline_refs(value);
+        Self::hoist_nested_defs(&mut inlined, &mut defs);
+        if !defs.is_empty()
+            && let Some(obj) = inlined.as_object_mut()
+        {
+            obj.insert("$defs".to_string(), serde_json::Value::Object(defs));
+        }
+        *value = inlined;
+        Ok(())
+    }
+
+    /// Move `$defs` attached to nested schemas into `defs` so every `#/$defs/...`
+    /// back-reference resolves against the document root
+    fn hoist_nested_defs(
+        value: &mut serde_json::Value,
+        defs: &mut serde_json::Map<String, serde_json::Value>,
+    ) {
         match value {
             serde_json::Value::Object(map) => {
-                // Check if this object contains a $ref
-                if let Some(ref_value) = map.get("$ref") {
-                    if let Some(ref_str) = ref_value.as_str() {
-                        if ref_str.starts_with("#/components/schemas/") {
-                            let schema_name = ref_str.trim_start_matches("#/components/schemas/");
-
-                            // Get the actual schema definition
-                            if let Some(components) = spec.json.get("components") {
-                                if let Some(schemas) = components.get("schemas") {
-                                    if let Some(schema_def) = schemas.get(schema_name) {
-                                        // Replace the entire object with the dereferenced schema
-                                        *value = schema_def.clone();
-                                        // Continue dereferencing in the new value
-                                        Self::dereference_schema_refs(value, spec)?;
-                                        return Ok(());
-                                    }
-                                }
-                            }
-                        }
+                if let Some(serde_json::Value::Object(nested)) = map.remove("$defs") {
+                    for (name, def) in nested {
+                        defs.entry(name).or_insert(def);
                     }
                 }
-
-                // Recursively process all values in the object
                 for (_, v) in map.iter_mut() {
-                    Self::dereference_schema_refs(v, spec)?;
+                    Self::hoist_nested_defs(v, defs);
                 }
             }
             serde_json::Value::Array(arr) => {
-                // Recursively process all items in the array
                 for item in arr.iter_mut() {
-                    Self::dereference_schema_refs(item, spec)?;
+                    Self::hoist_nested_defs(item, defs);
                 }
             }
-            _ => {} // Other types don't need processing
+            _ => {}
         }
-        Ok(())
+    }
+}
+
+/// URI scheme named after the project, e.g. `my-api` for `my_api`: lowercase,
+/// with characters RFC 3986 does not allow in a scheme replaced by `-`, and an
+/// `api-` prefix when the name does not start with a letter.
+fn resource_scheme(project_name: &str) -> String {
+    let scheme: String = project_name
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' | '+' | '-' | '.' => c,
+            _ => '-',
+        })
+        .collect();
+    if scheme.starts_with(|c: char| c.is_ascii_lowercase()) {
+        scheme
+    } else {
+        format!("api-{scheme}")
+    }
+}
+
+/// Choose the spec server to generate against and resolve every server's URL.
+///
+/// Returns the selected URL and `{url, description}` entries for the servers
+/// that resolve to an absolute URL, in spec order.
+fn select_server(spec: &OpenApiContext, config: &Config) -> Result<(String, Vec<JsonValue>)> {
+    let (servers, diagnostics) = spec.servers();
+    for diagnostic in &diagnostics {
+        warn!("{diagnostic}");
+    }
+    if servers.is_empty() {
+        return Err(crate::core::Error::Template(
+            "No server URL found in OpenAPI spec. Please define at least one server in the 'servers' section (OpenAPI 3.0+) or 'host' field (Swagger 2.0) of your OpenAPI specification".to_string()
+        ));
+    }
+
+    let selected = if let Some(index) = config.server_index {
+        if index >= servers.len() {
+            return Err(crate::core::Error::Template(format!(
+                "Server index {index} is out of range; the OpenAPI spec declares {} server(s)",
+                servers.len()
+            )));
+        }
+        index
+    } else if let Some(needle) = &config.server_url_match {
+        servers
+            .iter()
+            .position(|server| {
+                server.url.contains(needle.as_str())
+                    || resolve_server_url(server, &BTreeMap::new())
+                        .is_ok_and(|url| url.contains(needle.as_str()))
+            })
+            .ok_or_else(|| {
+                let urls: Vec<&str> = servers.iter().map(|server| server.url.as_str()).collect();
+                crate::core::Error::Template(format!(
+                    "No server URL in the OpenAPI spec contains '{needle}'. Available servers: {}",
+                    urls.join(", ")
+                ))
+            })?
+    } else {
+        0
+    };
+    let base_api_url = absolute_server_url(
+        &resolve_server_url(&servers[selected], &config.server_variables)?,
+        config,
+    )?;
+
+    let entries = servers
+        .iter()
+        .filter_map(|server| {
+            // Other servers take the overrides they accept and keep their defaults otherwise
+            let overrides: BTreeMap<String, String> = config
+                .server_variables
+                .iter()
+                .filter(|(name, value)| {
+                    server
+                        .variables
+                        .as_ref()
+                        .and_then(|vars| vars.get(name.as_str()))
+                        .is_some_and(|var| {
+                            var.enumeration.is_empty() || var.enumeration.contains(value)
+                        })
+                })
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+            let url = resolve_server_url(server, &overrides)
+                .and_then(|url| absolute_server_url(&url, config));
+            match url {
+                Ok(url) => Some(json!({
+                    "url": url,
+                    "description": server
+                        .description
+                        .as_deref()
+                        .unwrap_or_default()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                })),
+                Err(e) => {
+                    debug!(
+                        "Leaving server '{}' out of the server list: {e}",
+                        server.url
+                    );
+                    None
+                }
+            }
+        })
+        .collect();
+    Ok((base_api_url, entries))
+}
+
+/// Make a server URL absolute, prefixing relative paths with `--base-url`
+fn absolute_server_url(spec_url: &str, config: &Config) -> Result<String> {
+    if spec_url.starts_with("http://") || spec_url.starts_with("https://") {
+        // Spec contains a fully qualified URL, use it directly
+        Ok(spec_url.to_string())
+    } else if spec_url.starts_with('/') {
+        // Spec contains a relative path, combine with user-provided base URL
+        if let Some(base_url) = &config.base_url {
+            let base_str = base_url.to_string();
+            let trimmed = base_str.trim_end_matches('/');
+            Ok(format!("{trimmed}{spec_url}"))
+        } else {
+            Err(crate::core::Error::Template(format!(
+                "OpenAPI spec contains a relative server URL '{spec_url}', but no --base-url was provided. Please provide a base URL (e.g., --base-url https://api.example.com)"
+            )))
+        }
+    } else {
+        Err(crate::core::Error::Template(format!(
+            "Invalid server URL format in OpenAPI spec: '{spec_url}'. URL must be either a fully qualified URL (https://api.example.com/v1) or a relative path (/api/v1)"
+        )))
     }
 }
 
@@ -1334,4 +1486,89 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_dereference_schema_refs_emits_defs_for_cycles() {
+        let spec = OpenApiContext {
+            json: json!({"components": {"schemas": {"Node": {
+                "type": "object",
+                "properties": {
+                    "value": {"type": "string"},
+                    "next": {"$ref": "#/components/schemas/Node"}
+                }
+            }}}}),
+        };
+        let mut value = json!({
+            "responses": {"200": {"content": {"application/json": {
+                "schema": {"$ref": "#/components/schemas/Node"}
+            }}}},
+            "requestBody": {"content": {"application/json": {"schema": {
+                "$ref": "#/$defs/Node",
+                "$defs": {"Node": {"type": "object"}}
+            }}}}
+        });
+
+        TemplateManager::dereference_schema_refs(&mut value, &spec).unwrap();
+
+        let schema = &value["responses"]["200"]["content"]["application/json"]["schema"];
+        assert_eq!(schema["properties"]["next"]["$ref"], "#/$defs/Node");
+        assert_eq!(
+            value["$defs"]["Node"]["properties"]["next"]["$ref"],
+            "#/$defs/Node"
+        );
+        // $defs attached to nested schemas are moved to the document root
+        let body_schema = &value["requestBody"]["content"]["application/json"]["schema"];
+        assert!(body_schema.get("$defs").is_none());
+    }
+
+    #[test]
+    fn test_resource_scheme_is_a_valid_uri_scheme() {
+        assert_eq!(resource_scheme("petstore"), "petstore");
+        assert_eq!(resource_scheme("My_API v2"), "my-api-v2");
+        assert_eq!(resource_scheme("3d-shop"), "api-3d-shop");
+        assert_eq!(resource_scheme("café"), "caf-");
+    }
+
+    #[test]
+    fn test_select_server_applies_selection_and_variables() {
+        let spec = OpenApiContext {
+            json: json!({
+                "servers": [
+                    {"url": "https://prod.example.com/v1", "description": "Production\n  API"},
+                    {
+                        "url": "https://{region}.staging.example.com",
+                        "variables": {"region": {"default": "us", "enum": ["us", "eu"]}}
+                    },
+                    {"url": "/local"}
+                ]
+            }),
+        };
+        let mut config = Config::new("demo", "openapi.json", "out");
+
+        let (url, servers) = select_server(&spec, &config).unwrap();
+        assert_eq!(url, "https://prod.example.com/v1");
+        // The relative server cannot be resolved without --base-url
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0]["description"], "Production API");
+
+        config.server_url_match = Some("staging".to_string());
+        config.server_variables = BTreeMap::from([("region".to_string(), "eu".to_string())]);
+        let (url, servers) = select_server(&spec, &config).unwrap();
+        assert_eq!(url, "https://eu.staging.example.com");
+        assert_eq!(servers[1]["url"], "https://eu.staging.example.com");
+
+        // Overrides must be declared by the selected server
+        config.server_url_match = None;
+        assert!(select_server(&spec, &config).is_err());
+
+        config.server_variables.clear();
+        config.server_index = Some(2);
+        config.base_url = Some("https://localhost:8000".parse().unwrap());
+        let (url, servers) = select_server(&spec, &config).unwrap();
+        assert_eq!(url, "https://localhost:8000/local");
+        assert_eq!(servers.len(), 3);
+
+        config.server_index = Some(3);
+        assert!(select_server(&spec, &config).is_err());
+    }
 }
@@ -59,6 +59,17 @@ pub struct TemplateOptions {
     pub log_file: Option<String>,
 }
 
+impl TemplateOptions {
+    /// Whether the operation with `operation_id` passes the include/exclude lists
+    pub fn selects(&self, operation_id: &str) -> bool {
+        let operation_id = operation_id.to_string();
+        let included = self.all_operations
+            || self.include_operations.is_empty()
+            || self.include_operations.contains(&operation_id);
+        included && !self.exclude_operations.contains(&operation_id)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
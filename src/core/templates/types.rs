@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
 /// Parameter kind based on OpenAPI "in" field - language agnostic
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ParameterKind {
     Path,
@@ -18,12 +18,79 @@ pub enum ParameterKind {
     Cookie,
 }
 
+/// How a request body is encoded, chosen from its `content` media type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyEncoding {
+    /// `application/json` and `+json` media types
+    Json,
+    /// `multipart/form-data` and other `multipart` media types
+    Multipart,
+    /// `application/x-www-form-urlencoded`
+    Form,
+    /// `text` media types, sent as given
+    Text,
+    /// Any other media type, e.g. `application/octet-stream`, sent as a file
+    Binary,
+}
+
+impl BodyEncoding {
+    /// Encoding of `media_type`, ignoring its parameters and case
+    pub fn of(media_type: &str) -> Self {
+        let essence = media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match essence.as_str() {
+            "application/json" => Self::Json,
+            "application/x-www-form-urlencoded" => Self::Form,
+            _ if essence.ends_with("+json") => Self::Json,
+            _ if essence.starts_with("multipart/") => Self::Multipart,
+            _ if essence.starts_with("text/") => Self::Text,
+            _ => Self::Binary,
+        }
+    }
+}
+
 /// Language-agnostic parameter info with target language type
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TemplateParameterInfo {
+    /// Identifier for the parameter in the target language
     pub name: String,
+    /// Name of the parameter in the HTTP request, as written in the OpenAPI spec
+    pub wire_name: String,
+    /// Name of the tool argument; `wire_name` unless another argument already took it
+    pub argument_name: String,
     pub target_type: String,
     pub description: Option<String>,
     pub example: Option<JsonValue>,
     pub kind: ParameterKind,
+    /// Whether the parameter must be given; path parameters always are, unless
+    /// they have a default
+    pub required: bool,
+    /// Value sent when the argument is not given, as JSON
+    pub default_value: Option<String>,
+    /// Serialization style of the value: the spec's `style`, or else `form`
+    /// for query and cookie parameters and `simple` for path and header ones
+    pub style: String,
+    /// Whether an array is sent as one parameter per item; the spec's
+    /// `explode`, which defaults to true for the `form` style only
+    pub explode: bool,
+}
+
+/// Parameter the server fills in rather than the caller, from its config or a
+/// fixed value (`x-mcp` `config`, `hidden` and `default`)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateInjectedParameterInfo {
+    /// Name of the parameter in the HTTP request
+    pub wire_name: String,
+    pub kind: ParameterKind,
+    /// Key under `params` in the server config that supplies the value
+    pub config_key: Option<String>,
+    /// Value sent when the config supplies none, rendered as it goes on the wire
+    pub default_value: Option<String>,
+    /// Whether the request must not be sent without a value
+    pub required: bool,
 }
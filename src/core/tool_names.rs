@@ -0,0 +1,365 @@
+//! MCP tool names for the operations of a spec.
+//!
+//! Every operation becomes one tool, so its name must be unique across the
+//! whole server. MCP clients also limit tool names to 64 characters of
+//! `[a-zA-Z0-9_-]`. [`assign_tool_names`] derives a name for each operation
+//! with the chosen [`ToolNaming`] strategy, then fixes names that are invalid,
+//! too long, reserved or shared by several operations. Fixes append a short
+//! hash of the operation's method and path, so a name does not change when
+//! other operations are added to or removed from the spec. Every rename is
+//! reported as a [`Diagnostic`] explaining why it was made.
+
+// Internal imports (std, crate)
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::diagnostics::Diagnostic;
+use crate::core::error::{Error, Result};
+use crate::core::openapi::OpenApiOperation;
+use crate::core::utils::to_snake_case;
+
+// External imports (alphabetized)
+use serde::{Deserialize, Serialize};
+use tera::{Context, Tera};
+
+/// Longest tool name MCP clients accept
+pub const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Names of the tools every generated server provides on its own
+pub const RESERVED_TOOL_NAMES: &[&str] = &["ping"];
+
+/// Length of the hash suffix that makes a name unique, including its `_`
+const SUFFIX_LEN: usize = 9;
+
+/// How tool names are derived from operations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolNaming {
+    /// The operation's `operationId`, or its method and path when it has none
+    #[default]
+    OperationId,
+    /// The operation's method and path, e.g. `get_pets_pet_id`
+    MethodPath,
+    /// A Tera pattern with `operation_id`, `method`, `path`, `tag`, `tags` and
+    /// `summary` in scope, e.g. `{{ tag }}_{{ operation_id }}`
+    Pattern(String),
+}
+
+impl FromStr for ToolNaming {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "operation-id" => Ok(Self::OperationId),
+            "method-path" => Ok(Self::MethodPath),
+            pattern if pattern.contains("{{") => Ok(Self::Pattern(pattern.to_string())),
+            other => Err(format!(
+                "expected 'operation-id', 'method-path' or a Tera pattern, got '{other}'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ToolNaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OperationId => write!(f, "operation-id"),
+            Self::MethodPath => write!(f, "method-path"),
+            Self::Pattern(pattern) => write!(f, "{pattern}"),
+        }
+    }
+}
+
+impl ToolNaming {
+    /// The name `op` asks for, before it is made valid and unique.
+    ///
+    /// A name given by the operation's `x-mcp` extension wins over the strategy.
+    fn raw_name(&self, op: &OpenApiOperation) -> Result<String> {
+        if let Some(name) = &op.mcp.name {
+            return Ok(name.clone());
+        }
+        match self {
+            Self::OperationId => Ok(op.id.clone()),
+            Self::MethodPath => Ok(format!("{}_{}", op.method.as_str(), op.path)),
+            Self::Pattern(pattern) => {
+                let tags = op.tags.clone().unwrap_or_default();
+                let mut context = Context::new();
+                context.insert("operation_id", &op.id);
+                context.insert("method", op.method.as_str());
+                context.insert("path", &op.path);
+                context.insert("tag", tags.first().map(String::as_str).unwrap_or_default());
+                context.insert("tags", &tags);
+                context.insert("summary", op.summary.as_deref().unwrap_or_default());
+                Tera::one_off(pattern, &context, false).map_err(|e| {
+                    Error::template(format!("Invalid tool name pattern '{pattern}': {e}"))
+                })
+            }
+        }
+    }
+}
+
+/// Set the `tool_name` of every operation, returning an explanation for each
+/// name that differs from what the strategy produced.
+///
+/// Names are snake_case, start with a letter and fit [`MAX_TOOL_NAME_LEN`].
+/// The result only depends on the spec, never on the order of operations.
+pub fn assign_tool_names(
+    operations: &mut [OpenApiOperation],
+    naming: &ToolNaming,
+) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let mut names = Vec::with_capacity(operations.len());
+    for op in operations.iter() {
+        let raw = naming.raw_name(op)?;
+        let mut name = to_snake_case(&ascii_word_chars(&raw));
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name = format!("op_{name}").trim_end_matches('_').to_string();
+            diagnostics.push(Diagnostic::warning(
+                op.pointer(),
+                format!("Tool name '{raw}' does not start with a letter; renamed to '{name}'"),
+            ));
+        }
+        if name.len() > MAX_TOOL_NAME_LEN {
+            let shortened = with_suffix(&name, &operation_key(op));
+            diagnostics.push(Diagnostic::warning(
+                op.pointer(),
+                format!(
+                    "Tool name '{name}' is longer than {MAX_TOOL_NAME_LEN} characters; shortened to '{shortened}'"
+                ),
+            ));
+            name = shortened;
+        }
+        names.push(name);
+    }
+
+    // Operations by the name they would get
+    let mut claims: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (index, name) in names.iter().enumerate() {
+        claims.entry(name).or_default().push(index);
+    }
+    let mut final_names = names.clone();
+    for (name, indices) in &claims {
+        let reserved = RESERVED_TOOL_NAMES.contains(name);
+        if indices.len() == 1 && !reserved {
+            continue;
+        }
+        for &index in indices {
+            let op = &operations[index];
+            let renamed = with_suffix(name, &operation_key(op));
+            let reason = if reserved {
+                "is reserved for a built-in tool".to_string()
+            } else {
+                let others: Vec<String> = indices
+                    .iter()
+                    .filter(|&&other| other != index)
+                    .map(|&other| format!("'{}'", operation_key(&operations[other])))
+                    .collect();
+                format!("is also derived for {}", others.join(", "))
+            };
+            diagnostics.push(Diagnostic::warning(
+                op.pointer(),
+                format!("Tool name '{name}' {reason}; renamed to '{renamed}'"),
+            ));
+            final_names[index] = renamed;
+        }
+    }
+
+    // A suffixed name can, very rarely, equal a name the spec already uses.
+    // Names left as derived keep it, then suffixed names are settled by
+    // operation, so the outcome does not depend on the order of operations.
+    let mut order: Vec<usize> = (0..operations.len()).collect();
+    order.sort_by_cached_key(|&index| {
+        (
+            final_names[index] != names[index],
+            operation_key(&operations[index]),
+        )
+    });
+    let mut taken: BTreeMap<String, usize> = BTreeMap::new();
+    for index in order {
+        let op = &operations[index];
+        let mut name = final_names[index].clone();
+        let mut attempt = 2;
+        while taken.contains_key(&name) || RESERVED_TOOL_NAMES.contains(&name.as_str()) {
+            let key = format!("{} {attempt}", operation_key(op));
+            name = with_suffix(&names[index], &key);
+            attempt += 1;
+        }
+        if name != final_names[index] {
+            let reason = match taken.get(&final_names[index]) {
+                Some(&other) => {
+                    format!("is already used by '{}'", operation_key(&operations[other]))
+                }
+                None => "is reserved for a built-in tool".to_string(),
+            };
+            diagnostics.push(Diagnostic::warning(
+                op.pointer(),
+                format!(
+                    "Tool name '{}' {reason}; renamed to '{name}'",
+                    final_names[index]
+                ),
+            ));
+        }
+        taken.insert(name, index);
+    }
+    for (name, index) in taken {
+        operations[index].tool_name = name;
+    }
+    Ok(diagnostics)
+}
+
+/// Replaces every character MCP does not allow in tool names with `_`
+fn ascii_word_chars(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// What identifies an operation within a spec, e.g. `GET /pets/{id}`
+fn operation_key(op: &OpenApiOperation) -> String {
+    format!("{} {}", op.method.as_str().to_uppercase(), op.path)
+}
+
+/// Append a hash of `key` to `name`, shortening it to fit the length limit
+fn with_suffix(name: &str, key: &str) -> String {
+    let base = name[..name.len().min(MAX_TOOL_NAME_LEN - SUFFIX_LEN)].trim_end_matches('_');
+    format!("{base}_{:08x}", fnv1a(key))
+}
+
+/// 32-bit FNV-1a, which unlike `std`'s hasher is stable across releases
+fn fnv1a(text: &str) -> u32 {
+    text.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn operation(id: &str, method: &str, path: &str) -> OpenApiOperation {
+        serde_json::from_value(json!({
+            "operationId": id,
+            "path": path,
+            "method": method,
+            "tags": ["pets"],
+            "responses": {}
+        }))
+        .unwrap()
+    }
+
+    fn tool_names(operations: &[OpenApiOperation]) -> Vec<&str> {
+        operations.iter().map(|op| op.tool_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_assign_tool_names_with_each_strategy() {
+        let mut operations = vec![
+            operation("listPets", "get", "/pets"),
+            operation("showPetById", "get", "/pets/{petId}"),
+        ];
+
+        let diagnostics = assign_tool_names(&mut operations, &ToolNaming::OperationId).unwrap();
+        assert!(diagnostics.is_empty());
+        assert_eq!(tool_names(&operations), ["list_pets", "show_pet_by_id"]);
+
+        assign_tool_names(&mut operations, &ToolNaming::MethodPath).unwrap();
+        assert_eq!(tool_names(&operations), ["get_pets", "get_pets_pet_id"]);
+
+        let pattern: ToolNaming = "{{ tag }}-{{ operation_id }}".parse().unwrap();
+        assign_tool_names(&mut operations, &pattern).unwrap();
+        assert_eq!(
+            tool_names(&operations),
+            ["pets_list_pets", "pets_show_pet_by_id"]
+        );
+
+        let broken = ToolNaming::Pattern("{{ missing }}".to_string());
+        assert!(assign_tool_names(&mut operations, &broken).is_err());
+        assert!("by-tag".parse::<ToolNaming>().is_err());
+    }
+
+    #[test]
+    fn test_assign_tool_names_disambiguates_collisions() {
+        let mut operations = vec![
+            operation("getPets", "get", "/pets"),
+            operation("get_pets", "post", "/pets"),
+            operation("ping", "get", "/ping"),
+            operation("3dModel", "get", "/3d"),
+        ];
+        let diagnostics = assign_tool_names(&mut operations, &ToolNaming::OperationId).unwrap();
+
+        let names: Vec<String> = tool_names(&operations)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(names[0], format!("get_pets_{:08x}", fnv1a("GET /pets")));
+        assert_eq!(names[1], format!("get_pets_{:08x}", fnv1a("POST /pets")));
+        assert_eq!(names[2], format!("ping_{:08x}", fnv1a("GET /ping")));
+        assert_eq!(names[3], "op_3d_model");
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(diagnostics[0].pointer, "/paths/~13d/get");
+        assert!(
+            diagnostics[1]
+                .message
+                .contains("also derived for 'POST /pets'")
+        );
+        assert!(diagnostics[3].message.contains("reserved"));
+
+        // Names do not depend on the order of operations
+        operations.reverse();
+        assign_tool_names(&mut operations, &ToolNaming::OperationId).unwrap();
+        assert_eq!(operations[3].tool_name, names[0]);
+    }
+
+    #[test]
+    fn test_assign_tool_names_resolves_clashes_with_suffixed_names() {
+        // The name `get_pets` gets for `GET /pets` is the operationId of another operation
+        let suffixed = format!("get_pets_{:08x}", fnv1a("GET /pets"));
+        let mut operations = vec![
+            operation("getPets", "get", "/pets"),
+            operation("get_pets", "post", "/pets"),
+            operation(&suffixed, "get", "/other"),
+        ];
+        let diagnostics = assign_tool_names(&mut operations, &ToolNaming::OperationId).unwrap();
+
+        let names: Vec<String> = tool_names(&operations)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(names[0], format!("get_pets_{:08x}", fnv1a("GET /pets 2")));
+        assert_eq!(names[1], format!("get_pets_{:08x}", fnv1a("POST /pets")));
+        assert_eq!(names[2], suffixed);
+        let clash = diagnostics.last().unwrap();
+        assert_eq!(clash.pointer, "/paths/~1pets/get");
+        assert_eq!(
+            clash.message,
+            format!(
+                "Tool name '{suffixed}' is already used by 'GET /other'; renamed to '{}'",
+                names[0]
+            )
+        );
+
+        operations.reverse();
+        assign_tool_names(&mut operations, &ToolNaming::OperationId).unwrap();
+        let mut reversed: Vec<String> = tool_names(&operations)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        reversed.reverse();
+        assert_eq!(reversed, names);
+    }
+
+    #[test]
+    fn test_assign_tool_names_shortens_long_names() {
+        let long_id = "retrieveTheCompleteListOfEveryInvoiceLineItemForTheGivenCustomerAccount";
+        let mut operations = vec![operation(long_id, "get", "/invoices")];
+        let diagnostics = assign_tool_names(&mut operations, &ToolNaming::OperationId).unwrap();
+
+        let name = &operations[0].tool_name;
+        assert_eq!(name.len(), MAX_TOOL_NAME_LEN);
+        assert!(name.starts_with("retrieve_the_complete_list"));
+        assert!(name.ends_with(&format!("_{:08x}", fnv1a("GET /invoices"))));
+        assert!(diagnostics[0].message.contains("longer than 64 characters"));
+    }
+}
@@ -0,0 +1,479 @@
+//! Spec checks that flag problems which would produce broken or poor MCP tools.
+//!
+//! [`validate`] runs before any code is generated. On top of the diagnostics
+//! produced while parsing (malformed parameters, responses, security schemes) it
+//! looks for problems the generator tolerates but that lead to surprising tools:
+//! derived or renamed tool names, missing server URLs, unresolved references,
+//! untyped results and hidden parameters that never get a value. Operations
+//! hidden with `x-mcp` are not checked.
+
+// Internal imports (std, crate)
+use std::collections::BTreeMap;
+
+use crate::core::bundler::fragment_to_pointer;
+use crate::core::diagnostics::{Diagnostic, pointer_join};
+use crate::core::error::{Error, Result};
+use crate::core::events::collect_events;
+use crate::core::openapi::{OpenApiContext, OpenApiOperation, resolve_server_url};
+use crate::core::tool_names::{ToolNaming, assign_tool_names};
+
+// External imports (alphabetized)
+use openapiv3::Server;
+use serde_json::Value as JsonValue;
+
+/// Check a loaded spec, returning every problem found, ordered by location.
+///
+/// `loaded` holds the problems found while loading the spec, such as references
+/// the bundler could not resolve; they are part of the result. Tool names are
+/// derived with `naming`, as the generator would, so that any renames it would
+/// make are reported too.
+pub async fn validate(
+    spec: &OpenApiContext,
+    loaded: Vec<Diagnostic>,
+    naming: &ToolNaming,
+) -> Result<Vec<Diagnostic>> {
+    let (mut operations, parsed) = spec.parse_operations_with_diagnostics().await?;
+    // Parsing warns again about the references the bundler left in place
+    let reported: Vec<String> = loaded.iter().map(|d| d.pointer.clone()).collect();
+    let mut diagnostics = loaded;
+    diagnostics.extend(
+        parsed
+            .into_iter()
+            .filter(|d| !reported.contains(&d.pointer)),
+    );
+    operations.retain(|op| !op.mcp.hidden);
+    diagnostics.extend(assign_tool_names(&mut operations, naming)?);
+    diagnostics.extend(collect_events(spec, &operations).1);
+    diagnostics.extend(spec.security_schemes().1);
+    check_servers(spec, &mut diagnostics);
+    check_refs(spec, &spec.json, "", &mut diagnostics);
+
+    for op in &operations {
+        let pointer = op.pointer();
+        let Some(raw) = spec.json.pointer(&pointer) else {
+            continue;
+        };
+
+        if raw.get("operationId").is_none() && *naming == ToolNaming::OperationId {
+            diagnostics.push(Diagnostic::warning(
+                &pointer,
+                format!(
+                    "Operation has no operationId; its tool name is derived from the path as '{}'",
+                    op.tool_name
+                ),
+            ));
+        }
+
+        check_json_success_response(op, &pointer, &mut diagnostics);
+        check_injected_parameters(op, &pointer, &mut diagnostics);
+        check_parameters(spec, &pointer, &mut diagnostics);
+    }
+
+    diagnostics.sort_by(|a, b| {
+        a.pointer
+            .cmp(&b.pointer)
+            .then(b.severity.cmp(&a.severity))
+            .then(a.message.cmp(&b.message))
+    });
+    // Path-level parameters are seen once per operation of the path
+    diagnostics.dedup();
+    Ok(diagnostics)
+}
+
+/// Follow local `$ref`s from `value`, returning the target and where it lives
+fn follow_refs<'a>(
+    spec: &'a OpenApiContext,
+    pointer: String,
+    value: &'a JsonValue,
+) -> (String, &'a JsonValue) {
+    let (mut pointer, mut value) = (pointer, value);
+    // Bounded so that a reference cycle cannot loop forever
+    for _ in 0..32 {
+        let Some(ref_str) = value.get("$ref").and_then(JsonValue::as_str) else {
+            break;
+        };
+        let Some(target) = spec.resolve_ref(ref_str) else {
+            break;
+        };
+        pointer = fragment_to_pointer(ref_str.trim_start_matches('#'));
+        value = target;
+    }
+    (pointer, value)
+}
+
+/// Check that every server resolves to a URL the generated code can call
+fn check_servers(spec: &OpenApiContext, diagnostics: &mut Vec<Diagnostic>) {
+    diagnostics.extend(spec.servers().1);
+    let Some(entries) = spec
+        .json
+        .get("servers")
+        .and_then(JsonValue::as_array)
+        .filter(|entries| !entries.is_empty())
+    else {
+        if spec.base_path().is_none() {
+            diagnostics.push(Diagnostic::error(
+                "/servers",
+                "No server URL is declared, so generated tools would not know where to send requests",
+            ));
+        }
+        return;
+    };
+
+    for (index, entry) in entries.iter().enumerate() {
+        // Entries that do not parse were already reported above
+        let Ok(server) = serde_json::from_value::<Server>(entry.clone()) else {
+            continue;
+        };
+        let pointer = format!("/servers/{index}");
+        for (name, variable) in server.variables.iter().flatten() {
+            if !variable.enumeration.is_empty() && !variable.enumeration.contains(&variable.default)
+            {
+                diagnostics.push(Diagnostic::warning(
+                    pointer_join(&pointer_join(&pointer, "variables"), name) + "/default",
+                    format!(
+                        "Default '{}' of server variable '{name}' is not one of its allowed values",
+                        variable.default
+                    ),
+                ));
+            }
+        }
+
+        let url_pointer = pointer_join(&pointer, "url");
+        match resolve_server_url(&server, &BTreeMap::new()) {
+            Ok(url) if url.starts_with('/') => diagnostics.push(Diagnostic::warning(
+                url_pointer,
+                format!(
+                    "Server URL '{url}' is relative; generation needs --base-url to complete it"
+                ),
+            )),
+            Ok(url) if !url.starts_with("http://") && !url.starts_with("https://") => diagnostics
+                .push(Diagnostic::error(
+                    url_pointer,
+                    format!("Server URL '{url}' is neither an absolute http(s) URL nor a path"),
+                )),
+            Ok(_) => {}
+            Err(Error::OpenApi(message)) => {
+                diagnostics.push(Diagnostic::error(url_pointer, message))
+            }
+            Err(e) => diagnostics.push(Diagnostic::error(url_pointer, e.to_string())),
+        }
+    }
+}
+
+/// Report every local `$ref` in the document that does not resolve
+fn check_refs(
+    spec: &OpenApiContext,
+    value: &JsonValue,
+    pointer: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map {
+                let child_pointer = pointer_join(pointer, key);
+                match (key.as_str(), child) {
+                    // `#/$defs/` refs point into the enclosing schema's own definitions
+                    ("$ref", JsonValue::String(ref_str))
+                        if !ref_str.starts_with("#/$defs/")
+                            && spec.resolve_ref(ref_str).is_none() =>
+                    {
+                        // Parsing may already have reported it at this location
+                        if !diagnostics.iter().any(|d| d.pointer == child_pointer) {
+                            diagnostics.push(Diagnostic::error(
+                                child_pointer,
+                                format!("Unresolved $ref '{ref_str}'"),
+                            ));
+                        }
+                    }
+                    _ => check_refs(spec, child, &child_pointer, diagnostics),
+                }
+            }
+        }
+        JsonValue::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                check_refs(
+                    spec,
+                    item,
+                    &pointer_join(pointer, &index.to_string()),
+                    diagnostics,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+fn check_json_success_response(
+    op: &OpenApiOperation,
+    pointer: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let has_json_success = op.responses.iter().any(|(status, response)| {
+        status.starts_with('2')
+            && response
+                .content
+                .iter()
+                .flatten()
+                .any(|(media_type, _)| is_json_media_type(media_type))
+    });
+    if !has_json_success {
+        diagnostics.push(Diagnostic::warning(
+            pointer_join(pointer, "responses"),
+            "No 2xx response with a JSON body; the tool's result will be untyped",
+        ));
+    }
+}
+
+/// Check that every required parameter hidden from the caller still gets a value
+fn check_injected_parameters(
+    op: &OpenApiOperation,
+    pointer: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for param in op.parameters.iter().flatten() {
+        let mcp = &param.mcp;
+        if param.required.unwrap_or(false)
+            && mcp.hidden
+            && mcp.default.is_none()
+            && mcp.config.is_none()
+        {
+            diagnostics.push(Diagnostic::warning(
+                pointer,
+                format!(
+                    "Required parameter '{}' is hidden without a default or config key; requests will omit it",
+                    param.name
+                ),
+            ));
+        }
+    }
+}
+
+/// Check the path-level and operation-level parameters of the operation at `pointer`
+fn check_parameters(spec: &OpenApiContext, pointer: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let item_pointer = pointer
+        .rsplit_once('/')
+        .map(|(item, _)| item)
+        .unwrap_or_default();
+    for owner in [item_pointer, pointer] {
+        let list_pointer = pointer_join(owner, "parameters");
+        let Some(params) = spec
+            .json
+            .pointer(&list_pointer)
+            .and_then(JsonValue::as_array)
+        else {
+            continue;
+        };
+        for (index, param) in params.iter().enumerate() {
+            let (param_pointer, param) =
+                follow_refs(spec, pointer_join(&list_pointer, &index.to_string()), param);
+            let Some(name) = param.get("name").and_then(JsonValue::as_str) else {
+                continue;
+            };
+            if param.get("schema").is_none() && param.get("content").is_none() {
+                diagnostics.push(Diagnostic::warning(
+                    &param_pointer,
+                    format!("Parameter '{name}' has no schema; it will be treated as a string"),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::diagnostics::Severity;
+    use serde_json::json;
+
+    fn messages_at<'a>(diagnostics: &'a [Diagnostic], pointer: &str) -> Vec<&'a str> {
+        diagnostics
+            .iter()
+            .filter(|d| d.pointer == pointer)
+            .map(|d| d.message.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_validate_clean_spec_has_no_diagnostics() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "servers": [{"url": "https://api.example.com"}],
+                "paths": {
+                    "/pets": {
+                        "get": {
+                            "operationId": "listPets",
+                            "parameters": [{"name": "limit", "in": "query", "schema": {"type": "integer"}}],
+                            "responses": {"200": {
+                                "description": "ok",
+                                "content": {"application/json": {"schema": {"type": "array"}}}
+                            }}
+                        }
+                    }
+                }
+            }),
+        };
+        assert_eq!(
+            validate(&spec, Vec::new(), &ToolNaming::default())
+                .await
+                .unwrap(),
+            Vec::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_checks_mcp_extensions() {
+        let ok = json!({"200": {
+            "description": "ok",
+            "content": {"application/json": {"schema": {"type": "object"}}}
+        }});
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "servers": [{"url": "https://api.example.com"}],
+                "paths": {"/pets": {
+                    "get": {
+                        "operationId": "listPets",
+                        "parameters": [
+                            {"name": "debug", "in": "query", "required": true, "schema": {"type": "boolean"}, "x-mcp": {"hidden": true}},
+                            {"name": "limit", "in": "query", "required": true, "schema": {"type": "integer"}, "x-mcp": {"hidden": true, "default": 20}}
+                        ],
+                        "responses": ok
+                    },
+                    "post": {"operationId": "addPet", "x-mcp": {"readOnly": true}, "responses": ok},
+                    // Hidden operations are not checked, and their names are free
+                    "put": {"operationId": "list_pets", "x-mcp": {"hidden": true}}
+                }}
+            }),
+        };
+        let diagnostics = validate(&spec, Vec::new(), &ToolNaming::default())
+            .await
+            .unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].pointer, "/paths/~1pets/get");
+        assert!(diagnostics[0].message.contains("'debug' is hidden"));
+        assert_eq!(diagnostics[1].pointer, "/paths/~1pets/post/x-mcp");
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_tool_problems() {
+        let ok = json!({"200": {
+            "description": "ok",
+            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+        }});
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "paths": {
+                    "/pets": {
+                        "parameters": [{"name": "type", "in": "query"}],
+                        "get": {"operationId": "getPets", "responses": ok},
+                        "post": {
+                            "operationId": "get_pets",
+                            "requestBody": {"content": {"application/json": {
+                                "schema": {"$ref": "#/components/schemas/Pet"}
+                            }}},
+                            "responses": {"204": {"description": "created"}}
+                        }
+                    },
+                    "/pets/{id}": {
+                        "get": {
+                            "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"$ref": "#/components/schemas/Id"}}],
+                            "responses": ok
+                        }
+                    },
+                    "/3d": {"get": {"operationId": "3dModel", "responses": ok}}
+                },
+                "components": {"schemas": {"Pet": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "self": {"type": "string"}}
+                }}}
+            }),
+        };
+        let diagnostics = validate(&spec, Vec::new(), &ToolNaming::default())
+            .await
+            .unwrap();
+
+        // Parameter and property names are converted to valid identifiers
+        assert!(messages_at(&diagnostics, "/components/schemas/Pet/properties/self").is_empty());
+        assert!(messages_at(&diagnostics, "/paths/~1pets/parameters/0/name").is_empty());
+        assert!(
+            messages_at(&diagnostics, "/paths/~13d/get")[0].contains("renamed to 'op_3d_model'")
+        );
+
+        let derived = messages_at(&diagnostics, "/paths/~1pets~1{id}/get");
+        assert!(derived[0].contains("derived from the path as 'get_pets_id'"));
+
+        let duplicate = messages_at(&diagnostics, "/paths/~1pets/post");
+        assert!(duplicate[0].contains("Tool name 'get_pets' is also derived for 'GET /pets'"));
+        assert!(
+            messages_at(&diagnostics, "/paths/~1pets/post/responses")[0]
+                .contains("No 2xx response")
+        );
+
+        // The path-level parameter is reported once, not once per operation
+        assert_eq!(
+            messages_at(&diagnostics, "/paths/~1pets/parameters/0").len(),
+            1
+        );
+
+        let unresolved = diagnostics
+            .iter()
+            .find(|d| d.pointer == "/paths/~1pets~1{id}/get/parameters/0/schema/$ref")
+            .unwrap();
+        assert!(unresolved.message.contains("#/components/schemas/Id"));
+
+        let servers = diagnostics
+            .iter()
+            .find(|d| d.pointer == "/servers")
+            .unwrap();
+        assert_eq!(servers.severity, Severity::Error);
+    }
+
+    #[tokio::test]
+    async fn test_validate_flags_relative_server_url() {
+        let spec = OpenApiContext {
+            json: json!({"openapi": "3.0.3", "servers": [{"url": "/api/v1"}], "paths": {}}),
+        };
+        let diagnostics = validate(&spec, Vec::new(), &ToolNaming::default())
+            .await
+            .unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].pointer, "/servers/0/url");
+    }
+
+    #[tokio::test]
+    async fn test_validate_checks_server_variables() {
+        let spec = OpenApiContext {
+            json: json!({
+                "openapi": "3.0.3",
+                "servers": [
+                    {
+                        "url": "https://{region}.example.com",
+                        "variables": {"region": {"default": "ap", "enum": ["us", "eu"]}}
+                    },
+                    {"url": "https://{tenant}.example.com"}
+                ],
+                "paths": {}
+            }),
+        };
+        let diagnostics = validate(&spec, Vec::new(), &ToolNaming::default())
+            .await
+            .unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].pointer,
+            "/servers/0/variables/region/default"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+        assert_eq!(diagnostics[1].pointer, "/servers/1/url");
+        assert!(diagnostics[1].message.contains("tenant"));
+    }
+}
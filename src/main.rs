@@ -6,12 +6,14 @@ mod mcp;
 
 // Internal imports (std, crate)
 use core::{
-    openapi::OpenApiContext,
+    diagnostics::Severity,
+    openapi::{HttpMethod, OpenApiContext},
     protocol::Protocol,
     templates::{
         ClientTemplateKind, ServerTemplateKind, TemplateManager, TemplateOptions,
         dir::resolve_output_dir,
     },
+    tool_names::ToolNaming,
 };
 use std::path::PathBuf;
 
@@ -30,6 +32,8 @@ struct Cli {
     pub command: Commands,
 }
 
+// Parsed once at startup, so the size of the largest variant does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(clap::Subcommand, Debug)]
 pub enum Commands {
     /// Scaffold servers and clients for various targets
@@ -37,6 +41,56 @@ pub enum Commands {
         #[command(subcommand)]
         target: TargetCommands,
     },
+    /// Check an OpenAPI specification for problems before generating from it
+    Validate {
+        /// Path or URL to OpenAPI schema (YAML or JSON)
+        #[arg(long)]
+        schema_path: String,
+        /// Output format for the report
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        /// How tool names are derived: operation-id, method-path or a Tera pattern
+        #[arg(long, default_value_t = ToolNaming::OperationId)]
+        tool_naming: ToolNaming,
+    },
+}
+
+/// How `validate` prints its findings
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// One line per issue, followed by a summary
+    Text,
+    /// A single JSON document, for CI pipelines
+    Json,
+}
+
+/// Which operations become tools; every flag is repeatable
+#[derive(clap::Args, Debug)]
+pub struct SelectionArgs {
+    /// Include only operations with this tag
+    #[arg(long = "include-tag", value_name = "TAG")]
+    include_tags: Vec<String>,
+    /// Exclude operations with this tag
+    #[arg(long = "exclude-tag", value_name = "TAG")]
+    exclude_tags: Vec<String>,
+    /// Include only this operation (operationId or tool name)
+    #[arg(long = "include-op", value_name = "OPERATION")]
+    include_operations: Vec<String>,
+    /// Exclude this operation (operationId or tool name)
+    #[arg(long = "exclude-op", value_name = "OPERATION")]
+    exclude_operations: Vec<String>,
+    /// Include only operations whose path matches this glob, e.g. "/pets/**"
+    #[arg(long = "include-path", value_name = "GLOB")]
+    include_paths: Vec<String>,
+    /// Exclude operations whose path matches this glob
+    #[arg(long = "exclude-path", value_name = "GLOB")]
+    exclude_paths: Vec<String>,
+    /// Include only operations with this HTTP method
+    #[arg(long = "include-method", value_name = "METHOD")]
+    include_methods: Vec<HttpMethod>,
+    /// Exclude operations with this HTTP method
+    #[arg(long = "exclude-method", value_name = "METHOD")]
+    exclude_methods: Vec<HttpMethod>,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -48,6 +102,8 @@ pub enum TargetCommands {
     },
 }
 
+// Parsed once at startup, like `Commands`
+#[allow(clippy::large_enum_variant)]
 #[derive(clap::Subcommand, Debug)]
 pub enum McpCommands {
     /// Generate MCP server from OpenAPI specification that exposes API endpoints as MCP tools
@@ -76,6 +132,21 @@ pub enum McpCommands {
         /// Base URL of the OpenAPI specification
         #[arg(long)]
         base_url: Option<Url>,
+        /// Server URL variable as KEY=VALUE, overriding the spec default (repeatable)
+        #[arg(long = "server-var", value_name = "KEY=VALUE", value_parser = parse_server_var)]
+        server_vars: Vec<(String, String)>,
+        /// Index of the spec server to generate against [default: 0]
+        #[arg(long, conflicts_with = "server_url_match")]
+        server_index: Option<usize>,
+        /// Use the first spec server whose URL contains this text
+        #[arg(long)]
+        server_url_match: Option<String>,
+        /// How tool names are derived: operation-id, method-path or a Tera pattern
+        /// such as "{{ tag }}_{{ operation_id }}"
+        #[arg(long, default_value_t = ToolNaming::OperationId)]
+        tool_naming: ToolNaming,
+        #[command(flatten)]
+        selection: SelectionArgs,
     },
     /// Generate MCP client that can connect to MCP servers (no OpenAPI spec required)
     Client {
@@ -96,13 +167,19 @@ pub enum McpCommands {
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
     // Initialize logging with default level INFO
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env().add_directive(Level::INFO.into()))
-        .init();
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env().add_directive(Level::INFO.into()));
+    if matches!(cli.command, Commands::Validate { .. }) {
+        // The report is printed on stdout, so keep logs out of its way
+        subscriber.with_writer(std::io::stderr).init();
+    } else {
+        subscriber.init();
+    }
 
     info!("Starting Agenterra CLI");
-    let cli = Cli::parse();
     match &cli.command {
         Commands::Scaffold { target } => match target {
             TargetCommands::Mcp { role } => match role {
@@ -115,6 +192,11 @@ async fn main() -> anyhow::Result<()> {
                     log_file,
                     port,
                     base_url,
+                    server_vars,
+                    server_index,
+                    server_url_match,
+                    tool_naming,
+                    selection,
                 } => {
                     generate_mcp_server(ServerGenParams {
                         project_name,
@@ -125,6 +207,11 @@ async fn main() -> anyhow::Result<()> {
                         log_file,
                         port,
                         base_url,
+                        server_vars,
+                        server_index,
+                        server_url_match,
+                        tool_naming,
+                        selection,
                     })
                     .await?
                 }
@@ -136,6 +223,11 @@ async fn main() -> anyhow::Result<()> {
                 } => generate_mcp_client(project_name, template, template_dir, output_dir).await?,
             },
         },
+        Commands::Validate {
+            schema_path,
+            format,
+            tool_naming,
+        } => validate_spec(schema_path, *format, tool_naming).await?,
     }
     Ok(())
 }
@@ -150,6 +242,19 @@ struct ServerGenParams<'a> {
     log_file: &'a Option<String>,
     port: &'a Option<u16>,
     base_url: &'a Option<Url>,
+    server_vars: &'a [(String, String)],
+    server_index: &'a Option<usize>,
+    server_url_match: &'a Option<String>,
+    tool_naming: &'a ToolNaming,
+    selection: &'a SelectionArgs,
+}
+
+/// Parse a `--server-var` value of the form `KEY=VALUE`
+fn parse_server_var(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{arg}'")),
+    }
 }
 
 /// Generate MCP server from OpenAPI specification
@@ -193,6 +298,7 @@ async fn generate_mcp_server(params: ServerGenParams<'_>) -> anyhow::Result<()>
     }
 
     // Create config
+    let selection = params.selection;
     let config = crate::core::config::Config {
         project_name: params.project_name.to_string(),
         openapi_schema_path: params.schema_path.to_string(),
@@ -202,10 +308,20 @@ async fn generate_mcp_server(params: ServerGenParams<'_>) -> anyhow::Result<()>
             .template_dir
             .as_ref()
             .map(|p| p.to_string_lossy().to_string()),
-        include_all: true,
-        include_operations: Vec::new(),
-        exclude_operations: Vec::new(),
+        include_all: false,
+        include_operations: selection.include_operations.clone(),
+        exclude_operations: selection.exclude_operations.clone(),
+        include_tags: selection.include_tags.clone(),
+        exclude_tags: selection.exclude_tags.clone(),
+        include_paths: selection.include_paths.clone(),
+        exclude_paths: selection.exclude_paths.clone(),
+        include_methods: selection.include_methods.clone(),
+        exclude_methods: selection.exclude_methods.clone(),
         base_url: params.base_url.clone(),
+        server_index: *params.server_index,
+        server_url_match: params.server_url_match.clone(),
+        server_variables: params.server_vars.iter().cloned().collect(),
+        tool_naming: params.tool_naming.clone(),
     };
 
     // Create template options
@@ -273,7 +389,17 @@ async fn generate_mcp_client(
         include_all: true,
         include_operations: Vec::new(),
         exclude_operations: Vec::new(),
+        include_tags: Vec::new(),
+        exclude_tags: Vec::new(),
+        include_paths: Vec::new(),
+        exclude_paths: Vec::new(),
+        include_methods: Vec::new(),
+        exclude_methods: Vec::new(),
         base_url: None,
+        server_index: None,
+        server_url_match: None,
+        server_variables: Default::default(),
+        tool_naming: ToolNaming::default(),
     };
 
     // Generate the client directly via TemplateManager
@@ -286,3 +412,55 @@ async fn generate_mcp_client(
     );
     Ok(())
 }
+
+/// Exit code used when `validate` finds errors in the spec
+const EXIT_SPEC_INVALID: i32 = 5;
+
+/// Check an OpenAPI specification and print the issues found.
+///
+/// Exits with [`EXIT_SPEC_INVALID`] when any issue is an error, so the command
+/// can gate CI pipelines; warnings alone do not fail it.
+async fn validate_spec(
+    schema_path: &str,
+    format: ReportFormat,
+    tool_naming: &ToolNaming,
+) -> anyhow::Result<()> {
+    let (spec, loaded) = OpenApiContext::from_file_or_url_with_diagnostics(schema_path)
+        .await
+        .context("Failed to load OpenAPI schema")?;
+    let diagnostics = core::validate::validate(&spec, loaded, tool_naming)
+        .await
+        .context("Failed to validate OpenAPI schema")?;
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    match format {
+        ReportFormat::Json => {
+            let report = serde_json::json!({
+                "schema_path": schema_path,
+                "errors": errors,
+                "warnings": warnings,
+                "diagnostics": diagnostics,
+            });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        ReportFormat::Text => {
+            for diagnostic in &diagnostics {
+                println!("{diagnostic}");
+            }
+            if diagnostics.is_empty() {
+                println!("No problems found in {schema_path}");
+            } else {
+                println!("{errors} error(s), {warnings} warning(s) in {schema_path}");
+            }
+        }
+    }
+
+    if errors > 0 {
+        std::process::exit(EXIT_SPEC_INVALID);
+    }
+    Ok(())
+}
@@ -6,16 +6,23 @@
 //!
 //! The builder handles:
 //! - Converting OpenAPI identifiers to Rust naming conventions (snake_case, PascalCase)
-//! - Mapping OpenAPI types to Rust types (string -> String, integer -> i32, etc.)
+//! - Mapping OpenAPI types to Rust types (string -> String, int64 -> i64, etc.), generating
+//!   structs and enums for inline objects, string enums and unions
 //! - Organizing parameters and responses into Rust-appropriate structures
 //! - Generating type names for structs, enums, and functions
 
 use super::{EndpointContextBuilder, LanguageContextBuilder};
-use crate::core::openapi::OpenApiOperation;
-use crate::core::templates::{ParameterKind, TemplateParameterInfo};
+use crate::core::openapi::{
+    HttpMethod, OpenApiMediaType, OpenApiOperation, OpenApiParameter, merge_all_of, schema_example,
+};
+use crate::core::templates::{
+    BodyEncoding, ParameterKind, TemplateInjectedParameterInfo, TemplateParameterInfo,
+};
 use crate::core::utils::{to_proper_case, to_snake_case};
+use openapiv3::SecurityRequirement;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map as JsonMap, Value as JsonValue, json};
+use std::collections::BTreeMap;
 
 /// Rust-specific property information with type mapping.
 ///
@@ -25,6 +32,10 @@ use serde_json::{Map as JsonMap, Value as JsonValue, json};
 pub struct RustPropertyInfo {
     /// The property name in snake_case format
     pub name: String,
+    /// The property name exactly as it appears in the OpenAPI schema
+    pub wire_name: String,
+    /// Name of the tool argument for a request-body property; `wire_name` unless that clashes with a parameter
+    pub argument_name: String,
     /// The corresponding Rust type (e.g., "String", "i32", "bool")
     pub rust_type: String,
     /// Optional title from the OpenAPI schema
@@ -33,6 +44,110 @@ pub struct RustPropertyInfo {
     pub description: Option<String>,
     /// Optional example value from the OpenAPI schema
     pub example: Option<JsonValue>,
+    /// Whether the property is listed in the schema's `required` array
+    pub required: bool,
+    /// Whether the property is a file of a multipart body, given as base64 or a resource URI
+    pub file: bool,
+}
+
+/// A named Rust struct generated for an inline object or a recursive schema (one listed under `$defs`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustStructInfo {
+    /// The struct name in PascalCase
+    pub name: String,
+    /// Optional description from the OpenAPI schema
+    pub description: Option<String>,
+    /// Fields of the struct; self-references are boxed
+    pub properties: Vec<RustPropertyInfo>,
+}
+
+/// A Rust enum generated for a string `enum` or a `oneOf`/`anyOf` schema with several alternatives.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustEnumInfo {
+    /// The enum name in PascalCase
+    pub name: String,
+    /// Optional description from the OpenAPI schema
+    pub description: Option<String>,
+    /// How the enum is represented on the wire
+    pub kind: RustEnumKind,
+    /// Discriminator property of a tagged enum
+    pub tag: Option<String>,
+    /// One variant per alternative, in schema order
+    pub variants: Vec<RustVariantInfo>,
+}
+
+/// A documented 2xx response of an operation, one variant of its response enum.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustResponseInfo {
+    /// Status code as written in the spec, e.g. "201"
+    pub status: String,
+    /// Variant name in PascalCase, e.g. "Created" or "Status207"
+    pub variant: String,
+    /// Description of the response from the spec
+    pub description: Option<String>,
+    /// Rust type of a JSON body; responses without JSON content keep the raw response
+    pub rust_type: Option<String>,
+}
+
+/// The structured output of a tool, described by its `outputSchema`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustOutputInfo {
+    /// JSON Schema of the tool's `structuredContent`, always an object schema
+    pub schema: JsonValue,
+    /// Whether the response body is not an object and goes under a `result` property
+    pub wrapped: bool,
+}
+
+/// A GET operation exposed as an MCP resource, or as a resource template when
+/// its URI has variables.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustResourceInfo {
+    /// URI after `{scheme}://`: the operation path as an RFC 6570 template with
+    /// one variable per path argument, e.g. `pets/{petId}`
+    pub uri_path: String,
+    /// Whether the URI has variables, making the resource a resource template
+    pub template: bool,
+    /// Variables of the URI, in path order
+    pub variables: Vec<RustUriVariable>,
+    /// Whether some arguments are not URI variables and keep their defaults
+    pub partial: bool,
+    /// Media type shared by the documented 2xx responses, if any
+    pub mime_type: Option<String>,
+}
+
+/// A variable of a resource template, standing for a path argument
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustUriVariable {
+    /// Name in the URI template: the parameter name, or the field name when the
+    /// former is not a valid RFC 6570 variable name
+    pub name: String,
+    /// Field of the parameters struct the variable sets
+    pub field: String,
+}
+
+/// Serde representation of a [`RustEnumInfo`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RustEnumKind {
+    /// Internally tagged on the discriminator property
+    Tagged,
+    /// Untagged; the first variant the value fits wins
+    Untagged,
+    /// Unit variants, one per string value
+    Strings,
+}
+
+/// One alternative of a [`RustEnumInfo`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RustVariantInfo {
+    /// The variant name in PascalCase
+    pub name: String,
+    /// String value of the variant, or discriminator value that selects it (tagged enums)
+    pub wire_name: Option<String>,
+    /// Payload of a newtype variant; object alternatives become struct variants instead
+    pub rust_type: Option<String>,
+    /// Fields of a struct variant, without the discriminator property
+    pub properties: Vec<RustPropertyInfo>,
 }
 
 /// Complete Rust-specific context for code generation.
@@ -42,7 +157,14 @@ pub struct RustPropertyInfo {
 /// and structured data for template rendering.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RustEndpointContext {
-    /// Identifier for the endpoint (path with slashes replaced by '_')
+    /// Name of the MCP tool, unique across the server
+    pub tool_name: String,
+    /// Description of the MCP tool
+    pub tool_description: String,
+    /// MCP tool annotations from the HTTP method and the operation's `x-mcp`
+    /// extension, keyed by their `#[tool]` attribute name (e.g., `read_only_hint`)
+    pub annotations: BTreeMap<String, JsonValue>,
+    /// Identifier for the endpoint, used as its module name
     pub endpoint: String,
     /// Uppercase form of the endpoint for type names
     pub endpoint_cap: String,
@@ -50,6 +172,8 @@ pub struct RustEndpointContext {
     pub endpoint_fs: String,
     /// Raw path as defined in the OpenAPI spec (e.g., "/pet/{petId}")
     pub path: String,
+    /// HTTP method of the operation in lowercase (e.g., "get", "post")
+    pub method: String,
     /// Name of the generated function for the endpoint
     pub fn_name: String,
     /// Name of the generated parameters struct (e.g., 'users_params')
@@ -66,6 +190,25 @@ pub struct RustEndpointContext {
     pub properties_for_handler: Vec<String>,
     /// Typed list of parameters for the endpoint
     pub parameters: Vec<TemplateParameterInfo>,
+    /// Parameters the server fills in, which are not tool arguments
+    pub injected_parameters: Vec<TemplateInjectedParameterInfo>,
+    /// Properties of a JSON object request body, exposed as individual tool arguments
+    pub body_properties: Vec<RustPropertyInfo>,
+    /// Rust type of a request body that is not a JSON object, exposed as a single `body` argument
+    pub body_type: Option<String>,
+    /// Whether the request body must be given
+    pub body_required: bool,
+    /// How the request body is encoded; `None` without a request body
+    pub body_encoding: Option<BodyEncoding>,
+    /// Media type the request body is sent as, e.g. "multipart/form-data"
+    pub body_media_type: Option<String>,
+    /// Security requirements, any one of which authorizes a request: the
+    /// schemes it needs, with their OAuth2 scopes. Empty without security
+    pub security: Vec<SecurityRequirement>,
+    /// Structs for inline objects and recursive schemas of the parameters and request body
+    pub nested_types: Vec<RustStructInfo>,
+    /// Enums for string enums and `oneOf`/`anyOf` schemas of the parameters and request body
+    pub enum_types: Vec<RustEnumInfo>,
     /// Summary of the endpoint
     pub summary: String,
     /// Description of the endpoint
@@ -76,6 +219,13 @@ pub struct RustEndpointContext {
     pub properties_schema: JsonMap<String, JsonValue>,
     /// Schema reference for the response
     pub response_schema: JsonValue,
+    /// Documented 2xx responses, by status code
+    pub success_responses: Vec<RustResponseInfo>,
+    /// Structured output of the tool, when its 2xx responses share one JSON schema
+    pub output: Option<RustOutputInfo>,
+    /// The operation as an MCP resource, for GET operations whose only required
+    /// arguments are path parameters
+    pub resource: Option<RustResourceInfo>,
     /// Name of the spec file (if loaded from a file)
     pub spec_file_name: Option<String>,
     /// Valid fields for the endpoint
@@ -92,42 +242,42 @@ pub struct RustEndpointContextBuilder;
 
 impl EndpointContextBuilder for RustEndpointContextBuilder {
     fn build(&self, op: &OpenApiOperation) -> crate::core::error::Result<JsonValue> {
+        let arguments = extract_arguments(op);
+        let resource = extract_resource(op, &arguments);
         let context = RustEndpointContext {
-            fn_name: to_snake_case(&op.id),
-            parameters_type: to_proper_case(&format!("{}_params", op.id)),
-            endpoint: to_snake_case(&op.id),
-            endpoint_cap: to_proper_case(&op.id),
-            endpoint_fs: to_snake_case(&op.id),
+            tool_name: op.tool_name.clone(),
+            tool_description: tool_description(op),
+            annotations: tool_annotations(op),
+            fn_name: rust_item_name(&op.tool_name),
+            parameters_type: to_proper_case(&format!("{}_params", op.tool_name)),
+            endpoint: rust_item_name(&op.tool_name),
+            endpoint_cap: to_proper_case(&op.tool_name),
+            endpoint_fs: rust_item_name(&op.tool_name),
             path: op.path.clone(),
-            properties_type: to_proper_case(&format!("{}_properties", op.id)),
-            response_type: to_proper_case(&format!("{}_response", op.id)),
+            method: op.method.as_str().to_string(),
+            properties_type: to_proper_case(&format!("{}_properties", op.tool_name)),
+            response_type: to_proper_case(&format!("{}_response", op.tool_name)),
             envelope_properties: extract_envelope_properties(op),
             properties: extract_response_properties(op),
             properties_for_handler: extract_handler_properties(op),
-            parameters: op
-                .parameters
-                .clone()
-                .unwrap_or_default()
-                .into_iter()
-                .map(|p| TemplateParameterInfo {
-                    name: p.name,
-                    target_type: map_openapi_schema_to_rust_type(p.schema.as_ref()),
-                    description: p.description,
-                    example: p.example,
-                    kind: match p.in_.as_str() {
-                        "path" => ParameterKind::Path,
-                        "query" => ParameterKind::Query,
-                        "header" => ParameterKind::Header,
-                        "cookie" => ParameterKind::Cookie,
-                        _ => ParameterKind::Query, // Safe default
-                    },
-                })
-                .collect(),
+            parameters: arguments.parameters,
+            injected_parameters: arguments.injected_parameters,
+            body_properties: arguments.body_properties,
+            body_type: arguments.body_type,
+            body_required: arguments.body_required,
+            body_encoding: arguments.body_encoding,
+            body_media_type: arguments.body_media_type,
+            security: op.security.clone().unwrap_or_default(),
+            nested_types: arguments.nested_types,
+            enum_types: arguments.enum_types,
             summary: op.summary.clone().unwrap_or_default(),
             description: op.description.clone().unwrap_or_default(),
             tags: op.tags.clone().unwrap_or_default(),
             properties_schema: extract_properties_schema(op),
             response_schema: extract_response_schema(op),
+            success_responses: extract_success_responses(op),
+            output: extract_output(op),
+            resource,
             spec_file_name: extract_spec_file_name(op),
             valid_fields: extract_valid_fields(op),
         };
@@ -139,37 +289,971 @@ impl EndpointContextBuilder for RustEndpointContextBuilder {
 
 /// Maps OpenAPI schema types to appropriate Rust types.
 ///
-/// This function converts OpenAPI type definitions into their Rust equivalents,
-/// providing sensible defaults for cases where type information is missing or ambiguous.
-///
-/// # Arguments
-/// * `schema` - Optional reference to the OpenAPI schema JSON value
-///
-/// # Returns
-/// A String representing the appropriate Rust type
+/// This converts OpenAPI type definitions into their Rust equivalents, providing
+/// sensible defaults for cases where type information is missing or ambiguous.
+/// One mapper serves one operation. With `generate` set, inline objects become
+/// structs, and string enums and `oneOf`/`anyOf` schemas with several alternatives
+/// become enums. Each is named after where it appears (`{Op}Body{Property}`,
+/// `{Name}Item` for array items, `{Name}Value` for map values) and collected in
+/// `structs` or `enums`; without it they map to `serde_json::Value` and `String`.
 ///
 /// # Type Mappings
-/// - `string` → `String`
-/// - `integer` → `i32`
+/// - `string` → `String`; with format `date-time` → `chrono::DateTime<chrono::Utc>`,
+///   `date` → `chrono::NaiveDate`, `uuid` → `uuid::Uuid`, `binary` → `Vec<u8>`
+///   (`byte` stays a base64 `String`)
+/// - `integer` → `i32`; formats `int8`…`int64` and `uint8`…`uint64` → the sized type
 /// - `boolean` → `bool`
-/// - `number` → `f64`
-/// - Unknown/missing types → `String` (safe default)
-///
-fn map_openapi_schema_to_rust_type(schema: Option<&JsonValue>) -> String {
-    if let Some(sch) = schema {
-        if let Some(typ) = sch.get("type").and_then(|v| v.as_str()) {
-            match typ {
-                "string" => "String".to_string(),
-                "integer" => "i32".to_string(),
+/// - `number` → `f64`; format `float` → `f32`
+/// - `array` → `Vec<T>` with `T` mapped from `items` (`Option<T>` for nullable items)
+/// - `array` with `prefixItems` and no further items → tuple, e.g. `(f64, f64)`
+/// - `object` with only an `additionalProperties` schema → `std::collections::HashMap<String, T>`
+/// - `$ref` to `#/$defs/<Name>` (a recursive schema) → `Box<Name>`, or `Name` inside a `Vec`
+/// - OpenAPI 3.1 type arrays → the single non-null type (e.g. `["string", "null"]` → `String`)
+/// - `const` without `type` → the type of the constant value
+/// - `allOf` → the type of the merged schema
+/// - `oneOf`/`anyOf` with a single non-null alternative → the type of that alternative
+/// - multi-type unions, unresolved `$ref`s and unknown types → `serde_json::Value`
+/// - Missing types → `String` (safe default)
+#[derive(Debug, Default)]
+struct RustTypeMapper {
+    /// Whether to generate named types; without it they map to `serde_json::Value`/`String`
+    generate: bool,
+    /// Type names already taken in the generated module
+    reserved: Vec<String>,
+    /// Struct names of the recursive schemas, keyed by their `$defs` entry
+    def_names: BTreeMap<String, String>,
+    /// Generated structs
+    structs: Vec<RustStructInfo>,
+    /// Generated enums
+    enums: Vec<RustEnumInfo>,
+}
+
+/// Names a generated type must not take: those the handler module imports or
+/// uses unqualified
+const IMPORTED_TYPE_NAMES: &[&str] = &[
+    "Box",
+    "Config",
+    "Deserialize",
+    "HashMap",
+    "IntoCallToolResult",
+    "JsonSchema",
+    "Option",
+    "Result",
+    "Serialize",
+    "String",
+    "ToSchema",
+    "Vec",
+];
+
+impl RustTypeMapper {
+    /// A mapper that generates types, avoiding the given names
+    fn generating(reserved: Vec<String>) -> Self {
+        Self {
+            generate: true,
+            reserved,
+            ..Self::default()
+        }
+    }
+
+    /// Rust type of `schema`; a type generated for it is named after `name`
+    fn rust_type(&mut self, schema: &JsonValue, name: &str) -> String {
+        if let Some(def) = self.recursive_type_name(schema) {
+            return format!("Box<{def}>");
+        }
+        if schema.get("allOf").is_some() {
+            return self.rust_type(&merge_all_of(schema), name);
+        }
+        match composed_alternatives(schema).as_deref() {
+            Some([single]) => return self.rust_type(single, name),
+            Some([_, _, ..]) if self.generate => return self.union_type(schema, name),
+            _ => {}
+        }
+
+        match schema_types(schema).as_slice() {
+            [typ] => match *typ {
+                "string" => self.string_type(schema, name),
+                "integer" => integer_type(schema).to_string(),
                 "boolean" => "bool".to_string(),
+                "number" if schema_format(schema) == Some("float") => "f32".to_string(),
                 "number" => "f64".to_string(),
-                other => other.to_string(),
+                "array" => self.array_type(schema, name),
+                "object" => self.object_type(schema, name),
+                _ => "serde_json::Value".to_string(),
+            },
+            // `type: "null"` alone, or a union of several types
+            [] if schema.get("type").is_some() => "serde_json::Value".to_string(),
+            [_, _, ..] => "serde_json::Value".to_string(),
+            _ => {
+                if let Some(value) = schema.get("const") {
+                    const_type(value)
+                } else if string_enum_values(schema).is_some() {
+                    self.string_type(schema, name)
+                } else if ["properties", "additionalProperties"]
+                    .iter()
+                    .any(|key| schema.get(key).is_some())
+                {
+                    self.object_type(schema, name)
+                } else if ["oneOf", "anyOf", "$ref"]
+                    .iter()
+                    .any(|key| schema.get(key).is_some())
+                {
+                    "serde_json::Value".to_string()
+                } else {
+                    "String".to_string()
+                }
+            }
+        }
+    }
+
+    /// Maps a string schema by its `format`, or to a generated enum for its `enum` values
+    fn string_type(&mut self, schema: &JsonValue, name: &str) -> String {
+        if self.generate
+            && let Some(values) = string_enum_values(schema)
+        {
+            return self.string_enum(schema, name, &values);
+        }
+        match schema_format(schema) {
+            Some("date-time") => "chrono::DateTime<chrono::Utc>",
+            Some("date") => "chrono::NaiveDate",
+            Some("uuid") => "uuid::Uuid",
+            Some("binary") => "Vec<u8>",
+            _ => "String",
+        }
+        .to_string()
+    }
+
+    /// Maps an array schema to a `Vec` or, for closed `prefixItems`, a tuple
+    fn array_type(&mut self, schema: &JsonValue, name: &str) -> String {
+        if let Some(prefix) = schema.get("prefixItems").and_then(JsonValue::as_array) {
+            let closed = matches!(schema.get("items"), None | Some(JsonValue::Bool(false)));
+            if closed && !prefix.is_empty() {
+                let members: Vec<String> = prefix
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| self.rust_type(item, &format!("{name}Item{}", index + 1)))
+                    .collect();
+                return match members.as_slice() {
+                    [single] => format!("({single},)"),
+                    _ => format!("({})", members.join(", ")),
+                };
+            }
+            return "Vec<serde_json::Value>".to_string();
+        }
+
+        let Some(items) = schema.get("items").filter(|items| is_typed(items)) else {
+            return "Vec<serde_json::Value>".to_string();
+        };
+        // Vec already provides the indirection a recursive type needs
+        let item_type = match self.recursive_type_name(items) {
+            Some(def) => def,
+            None => self.rust_type(items, &format!("{name}Item")),
+        };
+        if is_nullable(items) {
+            format!("Vec<Option<{item_type}>>")
+        } else {
+            format!("Vec<{item_type}>")
+        }
+    }
+
+    /// Maps an object schema to a generated struct, or to a map when it only
+    /// constrains `additionalProperties`
+    fn object_type(&mut self, schema: &JsonValue, name: &str) -> String {
+        let has_properties = schema
+            .get("properties")
+            .and_then(JsonValue::as_object)
+            .is_some_and(|properties| !properties.is_empty());
+        if has_properties {
+            return if self.generate {
+                self.object_struct(schema, name)
+            } else {
+                "serde_json::Value".to_string()
+            };
+        }
+        match schema.get("additionalProperties") {
+            Some(values) if is_typed(values) => {
+                let value_type = self.rust_type(values, &format!("{name}Value"));
+                format!("std::collections::HashMap<String, {value_type}>")
+            }
+            _ => "serde_json::Value".to_string(),
+        }
+    }
+
+    /// Generates a struct for an inline object schema
+    fn object_struct(&mut self, schema: &JsonValue, name: &str) -> String {
+        let name = self.reserve(name);
+        let properties = self.properties(schema, &name);
+        self.structs.push(RustStructInfo {
+            name: name.clone(),
+            description: schema_description(schema),
+            properties,
+        });
+        name
+    }
+
+    /// Generates an enum with one unit variant per string value
+    fn string_enum(&mut self, schema: &JsonValue, name: &str, values: &[&str]) -> String {
+        let name = self.reserve(name);
+        let mut variants: Vec<RustVariantInfo> = Vec::new();
+        for (index, value) in values.iter().enumerate() {
+            let mut variant_name = Some(to_proper_case(&identifier_chars(value)))
+                .filter(|name| is_type_name(name))
+                .unwrap_or_else(|| format!("Value{}", index + 1));
+            if variants.iter().any(|v| v.name == variant_name) {
+                variant_name = format!("{variant_name}{}", index + 1);
+            }
+            variants.push(RustVariantInfo {
+                name: variant_name,
+                wire_name: Some(value.to_string()),
+                rust_type: None,
+                properties: Vec::new(),
+            });
+        }
+        self.enums.push(RustEnumInfo {
+            name: name.clone(),
+            description: schema_description(schema),
+            kind: RustEnumKind::Strings,
+            tag: None,
+            variants,
+        });
+        name
+    }
+
+    /// Generates an enum for a `oneOf`/`anyOf` schema with several alternatives.
+    ///
+    /// With a `discriminator` whose alternatives are all objects the enum is
+    /// internally tagged on the discriminator property. Otherwise it is untagged and
+    /// serde picks the first alternative, in schema order, that the value fits.
+    fn union_type(&mut self, schema: &JsonValue, name: &str) -> String {
+        let name = self.reserve(name);
+        let alternatives: Vec<JsonValue> = composed_alternatives(schema)
+            .unwrap_or_default()
+            .into_iter()
+            .map(merge_all_of)
+            .collect();
+        let tag = schema
+            .pointer("/discriminator/propertyName")
+            .and_then(JsonValue::as_str)
+            .filter(|_| {
+                alternatives
+                    .iter()
+                    .all(|alt| alt.get("properties").is_some() || schema_types(alt) == ["object"])
+            })
+            .map(String::from);
+
+        let mut variants: Vec<RustVariantInfo> = Vec::new();
+        for (index, alternative) in alternatives.iter().enumerate() {
+            let wire_name = tag
+                .as_deref()
+                .and_then(|tag| discriminator_value(alternative, tag));
+            let mut variant_name = variant_name(alternative, wire_name.as_deref(), index);
+            if variants.iter().any(|v| v.name == variant_name) {
+                variant_name = format!("{variant_name}{}", index + 1);
+            }
+            let payload_name = format!("{name}{variant_name}");
+            let (rust_type, properties) = if alternative.get("properties").is_some() {
+                let properties = self
+                    .properties(alternative, &payload_name)
+                    .into_iter()
+                    .filter(|prop| tag.as_ref() != Some(&prop.wire_name))
+                    .collect();
+                (None, properties)
+            } else {
+                (Some(self.rust_type(alternative, &payload_name)), Vec::new())
+            };
+            variants.push(RustVariantInfo {
+                name: variant_name,
+                wire_name,
+                rust_type,
+                properties,
+            });
+        }
+
+        self.enums.push(RustEnumInfo {
+            name: name.clone(),
+            description: schema_description(schema),
+            kind: if tag.is_some() {
+                RustEnumKind::Tagged
+            } else {
+                RustEnumKind::Untagged
+            },
+            tag,
+            variants,
+        });
+        name
+    }
+
+    /// Maps the properties of an object schema to fields; generated field types
+    /// are named `{parent}{Property}`
+    fn properties(&mut self, schema: &JsonValue, parent: &str) -> Vec<RustPropertyInfo> {
+        let schema = merge_all_of(schema);
+        let Some(properties) = schema.get("properties").and_then(JsonValue::as_object) else {
+            return Vec::new();
+        };
+        let required = schema.get("required").and_then(JsonValue::as_array);
+        let mut properties: Vec<RustPropertyInfo> = properties
+            .iter()
+            .map(|(prop_name, prop_schema)| {
+                let type_name = to_proper_case(&identifier_chars(&format!("{parent}_{prop_name}")));
+                let rust_type = self.rust_type(prop_schema, &type_name);
+                let mut property = property_info(prop_name, prop_schema, rust_type);
+                property.required =
+                    required.is_some_and(|required| required.iter().any(|name| name == prop_name));
+                property
+            })
+            .collect();
+        dedupe_field_names(properties.iter_mut().map(|prop| &mut prop.name));
+        properties
+    }
+
+    /// Generates structs for the recursive schemas (`$defs`) that `schema` refers back to.
+    ///
+    /// Only definitions reached through a `#/$defs/<Name>` back-reference become
+    /// structs; other 2020-12 `$defs` entries have already been inlined.
+    fn recursive_structs(&mut self, schema: &JsonValue) {
+        let mut referenced = Vec::new();
+        collect_recursive_refs(schema, &mut referenced);
+        let Some(defs) = schema.get("$defs").and_then(JsonValue::as_object) else {
+            return;
+        };
+        let defs: Vec<(&String, &JsonValue)> = defs
+            .iter()
+            .filter(|(key, _)| referenced.contains(key))
+            .collect();
+
+        // Name every struct first, so that fields can refer to any of them
+        for (key, _) in &defs {
+            let name = self.reserve(&to_proper_case(key));
+            self.def_names.insert(key.to_string(), name);
+        }
+        for (key, def) in defs {
+            let name = self.def_names[key.as_str()].clone();
+            let properties = self.properties(def, &name);
+            self.structs.push(RustStructInfo {
+                name,
+                description: schema_description(def),
+                properties,
+            });
+        }
+    }
+
+    /// Name of the struct generated for a `#/$defs/<Name>` back-reference
+    fn recursive_type_name(&self, schema: &JsonValue) -> Option<String> {
+        let key = recursive_ref_key(schema)?;
+        Some(
+            self.def_names
+                .get(&key)
+                .cloned()
+                .unwrap_or_else(|| to_proper_case(&key)),
+        )
+    }
+
+    /// Claims a unique, valid type name based on `name`
+    fn reserve(&mut self, name: &str) -> String {
+        let base = match to_proper_case(&identifier_chars(name)) {
+            _ if is_type_name(name) => name.to_string(),
+            proper if is_type_name(&proper) => proper,
+            proper => format!("Type{proper}"),
+        };
+        let taken = |candidate: &String| {
+            IMPORTED_TYPE_NAMES.contains(&candidate.as_str()) || self.reserved.contains(candidate)
+        };
+        let mut unique = base.clone();
+        let mut counter = 2;
+        while taken(&unique) {
+            unique = format!("{base}{counter}");
+            counter += 1;
+        }
+        self.reserved.push(unique.clone());
+        unique
+    }
+}
+
+/// Non-null types declared by a schema; `type` may be a string or (3.1) an array
+fn schema_types(schema: &JsonValue) -> Vec<&str> {
+    match schema.get("type") {
+        Some(JsonValue::String(typ)) if typ != "null" => vec![typ.as_str()],
+        Some(JsonValue::Array(types)) => types
+            .iter()
+            .filter_map(JsonValue::as_str)
+            .filter(|typ| *typ != "null")
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The `format` of a schema, if any
+fn schema_format(schema: &JsonValue) -> Option<&str> {
+    schema.get("format").and_then(JsonValue::as_str)
+}
+
+/// The `description` of a schema, if any
+fn schema_description(schema: &JsonValue) -> Option<String> {
+    schema
+        .get("description")
+        .and_then(JsonValue::as_str)
+        .map(String::from)
+}
+
+/// Whether a schema constrains its values at all (`{}` and `true` accept anything)
+fn is_typed(schema: &JsonValue) -> bool {
+    schema.as_object().is_some_and(|schema| {
+        schema
+            .keys()
+            .any(|key| !matches!(key.as_str(), "description" | "title" | "example"))
+    })
+}
+
+/// Keywords that are valid field names in raw form (`r#type`)
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be raw identifiers
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "super"];
+
+/// Methods of the generated server that tool functions must not clash with
+const SERVER_METHOD_NAMES: &[&str] = &["new", "tool_router"];
+
+/// Turns a tool name into the name of its handler module and function.
+///
+/// Tool names are already snake_case and start with a letter, so only keywords
+/// and the server's own methods need a trailing underscore (`type` → `type_`).
+fn rust_item_name(tool_name: &str) -> String {
+    if RUST_KEYWORDS.contains(&tool_name)
+        || NON_RAW_KEYWORDS.contains(&tool_name)
+        || SERVER_METHOD_NAMES.contains(&tool_name)
+    {
+        format!("{tool_name}_")
+    } else {
+        tool_name.to_string()
+    }
+}
+
+/// Replaces every character that cannot appear in an identifier with `_`
+fn identifier_chars(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Turns a parameter or property name into a valid snake_case Rust field name.
+///
+/// Punctuation separates words (`filter[status]` → `filter_status`), a leading
+/// digit gets an underscore (`2fa_code` → `_2fa_code`), and keywords become raw
+/// identifiers (`type` → `r#type`) or, where Rust forbids that, take a trailing
+/// underscore (`self` → `self_`).
+fn rust_field_name(name: &str) -> String {
+    match to_snake_case(&identifier_chars(name)).as_str() {
+        "" => "value".to_string(),
+        snake if snake.starts_with(|c: char| c.is_ascii_digit()) => format!("_{snake}"),
+        snake if NON_RAW_KEYWORDS.contains(&snake) => format!("{snake}_"),
+        snake if RUST_KEYWORDS.contains(&snake) => format!("r#{snake}"),
+        snake => snake.to_string(),
+    }
+}
+
+/// Numbers repeated field names (`id`, `id_2`), which distinct wire names can
+/// produce once converted (`X-Id` and `x_id`)
+fn dedupe_field_names<'a>(names: impl IntoIterator<Item = &'a mut String>) {
+    let mut seen: Vec<String> = Vec::new();
+    for name in names {
+        let base = name.trim_start_matches("r#").to_string();
+        let mut counter = 2;
+        while seen.contains(name) {
+            *name = format!("{base}_{counter}");
+            counter += 1;
+        }
+        seen.push(name.clone());
+    }
+}
+
+/// Whether `name` can be used as a generated type or variant name
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+        && name != "Self"
+}
+
+/// Maps an integer schema by its `format`
+fn integer_type(schema: &JsonValue) -> &'static str {
+    match schema_format(schema) {
+        Some("int8") => "i8",
+        Some("int16") => "i16",
+        Some("int64") => "i64",
+        Some("uint8") => "u8",
+        Some("uint16") => "u16",
+        Some("uint32") => "u32",
+        Some("uint64") => "u64",
+        _ => "i32",
+    }
+}
+
+/// The values of a string `enum`, ignoring a `null` member
+fn string_enum_values(schema: &JsonValue) -> Option<Vec<&str>> {
+    let values: Vec<&JsonValue> = schema
+        .get("enum")?
+        .as_array()?
+        .iter()
+        .filter(|value| !value.is_null())
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.into_iter().map(JsonValue::as_str).collect()
+}
+
+/// Whether a schema admits `null` (3.1 `type: [T, "null"]` or 3.0 `nullable: true`)
+fn is_nullable(schema: &JsonValue) -> bool {
+    let in_types = schema
+        .get("type")
+        .and_then(JsonValue::as_array)
+        .is_some_and(|types| types.iter().any(|t| t == "null"));
+    in_types || schema.get("nullable").and_then(JsonValue::as_bool) == Some(true)
+}
+
+/// Alternatives of a `oneOf`/`anyOf` schema, leaving out `null` ones
+fn composed_alternatives(schema: &JsonValue) -> Option<Vec<&JsonValue>> {
+    let alternatives = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema.get(key)?.as_array())?;
+    Some(
+        alternatives
+            .iter()
+            .filter(|alt| !(alt.get("type").is_some() && schema_types(alt).is_empty()))
+            .collect(),
+    )
+}
+
+/// Rust type of a `const` value
+fn const_type(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(_) => "String".to_string(),
+        JsonValue::Bool(_) => "bool".to_string(),
+        JsonValue::Number(n) if n.is_f64() => "f64".to_string(),
+        JsonValue::Number(_) => "i32".to_string(),
+        _ => "serde_json::Value".to_string(),
+    }
+}
+
+/// The `$defs` entry named by a `#/$defs/<Name>` back-reference
+fn recursive_ref_key(schema: &JsonValue) -> Option<String> {
+    schema
+        .get("$ref")
+        .and_then(JsonValue::as_str)
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+        .map(|key| key.replace("~1", "/").replace("~0", "~"))
+}
+
+/// Collects the `$defs` entries named by all back-references in `value`
+fn collect_recursive_refs(value: &JsonValue, keys: &mut Vec<String>) {
+    if let Some(key) = recursive_ref_key(value)
+        && !keys.contains(&key)
+    {
+        keys.push(key);
+    }
+    match value {
+        JsonValue::Object(map) => map
+            .values()
+            .for_each(|child| collect_recursive_refs(child, keys)),
+        JsonValue::Array(items) => items
+            .iter()
+            .for_each(|child| collect_recursive_refs(child, keys)),
+        _ => {}
+    }
+}
+
+/// Picks the media type a request body is sent as, with its encoding and schema.
+///
+/// JSON is preferred (`application/json` over `+json` types), then multipart,
+/// form and text; any other media type is sent as a file.
+fn request_body_media(op: &OpenApiOperation) -> Option<(BodyEncoding, &str, Option<&JsonValue>)> {
+    op.request_body
+        .as_ref()?
+        .content
+        .iter()
+        .map(|(media_type, media)| (BodyEncoding::of(media_type), media_type, media))
+        .min_by_key(|(encoding, media_type, _)| {
+            (*encoding, *media_type != "application/json", *media_type)
+        })
+        .map(|(encoding, media_type, media)| (encoding, media_type.as_str(), media.schema.as_ref()))
+}
+
+/// Whether a schema describes file contents: `format: binary`, or a
+/// `contentMediaType` without a `contentEncoding` (OpenAPI 3.1)
+fn is_file_schema(schema: &JsonValue) -> bool {
+    schema_format(schema) == Some("binary")
+        || (schema.get("contentMediaType").is_some() && schema.get("contentEncoding").is_none())
+}
+
+/// How file arguments are described to MCP clients
+const FILE_ARGUMENT_DESCRIPTION: &str =
+    "File contents as base64, a data: URI or a file:// resource URI";
+
+/// Typed tool arguments of an operation, with the types generated for them
+struct RustArguments {
+    parameters: Vec<TemplateParameterInfo>,
+    injected_parameters: Vec<TemplateInjectedParameterInfo>,
+    body_properties: Vec<RustPropertyInfo>,
+    body_type: Option<String>,
+    body_required: bool,
+    body_encoding: Option<BodyEncoding>,
+    body_media_type: Option<String>,
+    nested_types: Vec<RustStructInfo>,
+    enum_types: Vec<RustEnumInfo>,
+}
+
+/// Maps the parameters and request body of an operation to tool arguments.
+///
+/// The properties of an object body (JSON, multipart or form) become individual
+/// arguments; those whose snake_case name clashes with a parameter are prefixed
+/// with `body_`, both as a field and as an argument, while `wire_name` keeps the
+/// name used in the body. Any other JSON body keeps its mapped type as a single
+/// `body` argument, a text body is a string and a body of any other media type
+/// is a file. Files are strings holding base64 or a resource URI, which the
+/// generated server reads before sending the request. Body properties are only
+/// required when the request body itself is.
+fn extract_arguments(op: &OpenApiOperation) -> RustArguments {
+    let mut mapper = RustTypeMapper::generating(vec![
+        to_proper_case(&format!("{}_params", op.tool_name)),
+        to_proper_case(&format!("{}_properties", op.tool_name)),
+        to_proper_case(&format!("{}_response", op.tool_name)),
+    ]);
+    let media = request_body_media(op);
+    let body_encoding = media.map(|(encoding, _, _)| encoding);
+    let body_schema = media
+        .and_then(|(encoding, _, schema)| {
+            matches!(
+                encoding,
+                BodyEncoding::Json | BodyEncoding::Multipart | BodyEncoding::Form
+            )
+            .then_some(schema?)
+        })
+        .map(merge_all_of);
+    if let Some(schema) = &body_schema {
+        mapper.recursive_structs(schema);
+    }
+
+    // Parameters set through `x-mcp` are filled in by the server; hidden ones
+    // without a value are not sent at all
+    let (injected, exposed): (Vec<_>, Vec<_>) = op
+        .parameters
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .partition(|p| p.mcp.is_injected());
+    let injected_parameters = injected
+        .into_iter()
+        .filter(|p| p.mcp.config.is_some() || p.mcp.default.is_some())
+        .map(|p| TemplateInjectedParameterInfo {
+            kind: parameter_kind(&p.in_),
+            config_key: p.mcp.config.clone(),
+            default_value: p.mcp.default.as_ref().map(wire_value),
+            required: p.in_ == "path" || p.required == Some(true),
+            wire_name: p.name,
+        })
+        .collect();
+
+    let mut parameters: Vec<TemplateParameterInfo> = exposed
+        .into_iter()
+        .map(|p| TemplateParameterInfo {
+            style: parameter_style(&p),
+            explode: p.explode.unwrap_or(parameter_style(&p) == "form"),
+            target_type: match &p.schema {
+                Some(schema) => {
+                    let type_name =
+                        to_proper_case(&identifier_chars(&format!("{}_{}", op.tool_name, p.name)));
+                    mapper.rust_type(schema, &type_name)
+                }
+                None => "String".to_string(),
+            },
+            example: p
+                .example
+                .or_else(|| p.schema.as_ref().and_then(schema_example)),
+            required: (p.in_ == "path" || p.required == Some(true)) && p.mcp.default.is_none(),
+            kind: parameter_kind(&p.in_),
+            default_value: p.mcp.default.as_ref().map(JsonValue::to_string),
+            description: match (&p.description, &p.mcp.default) {
+                (Some(description), Some(default)) => Some(format!(
+                    "{} (default: {})",
+                    description.trim_end(),
+                    wire_value(default)
+                )),
+                (None, Some(default)) => Some(format!("Default: {}", wire_value(default))),
+                (description, None) => description.clone(),
+            },
+            name: rust_field_name(&p.name),
+            argument_name: p.name.clone(),
+            wire_name: p.name,
+        })
+        .collect();
+
+    let body_name = to_proper_case(&format!("{}_body", op.tool_name));
+    let body_required = op
+        .request_body
+        .as_ref()
+        .is_some_and(|body| body.required == Some(true));
+    let (mut body_properties, body_type) = match (body_encoding, body_schema) {
+        (None, _) => (Vec::new(), None),
+        (Some(BodyEncoding::Text | BodyEncoding::Binary), _) => {
+            (Vec::new(), Some("String".to_string()))
+        }
+        (Some(encoding), Some(schema)) if schema.get("properties").is_some() => {
+            let param_names: Vec<String> = parameters
+                .iter()
+                .map(|p| rust_field_name(&p.wire_name))
+                .collect();
+            let mut properties = mapper.properties(&schema, &body_name);
+            for prop in &mut properties {
+                if param_names.contains(&prop.name) {
+                    prop.name = format!("body_{}", prop.name.trim_start_matches("r#"));
+                }
+                prop.required &= body_required;
+                if encoding != BodyEncoding::Json {
+                    form_field_type(prop, &schema["properties"][&prop.wire_name], encoding);
+                }
             }
+            (properties, None)
+        }
+        (Some(BodyEncoding::Json), Some(schema)) => {
+            (Vec::new(), Some(mapper.rust_type(&schema, &body_name)))
+        }
+        _ => (Vec::new(), Some("serde_json::Value".to_string())),
+    };
+
+    // Parameters, body properties and a whole `body` all become fields of the one
+    // parameters struct and arguments of the one tool
+    let body_field = body_type.as_ref().map(|_| String::from("body"));
+    let mut fields: Vec<(&mut String, &String, &mut String)> = parameters
+        .iter_mut()
+        .map(|p| (&mut p.name, &p.wire_name, &mut p.argument_name))
+        .chain(
+            body_properties
+                .iter_mut()
+                .map(|prop| (&mut prop.name, &prop.wire_name, &mut prop.argument_name)),
+        )
+        .collect();
+    dedupe_field_names(
+        body_field
+            .clone()
+            .iter_mut()
+            .chain(fields.iter_mut().map(|(name, _, _)| &mut **name)),
+    );
+    // An argument keeps its wire name unless an earlier one took it; then it
+    // follows the (unique) field name
+    let mut arguments: Vec<String> = body_field.into_iter().collect();
+    for (name, wire_name, argument_name) in &mut fields {
+        let mut argument = if arguments.contains(wire_name) {
+            name.trim_start_matches("r#").to_string()
         } else {
-            "String".to_string()
+            wire_name.to_string()
+        };
+        let mut counter = 2;
+        while arguments.contains(&argument) {
+            argument = format!("{}_{counter}", name.trim_start_matches("r#"));
+            counter += 1;
+        }
+        **argument_name = argument.clone();
+        arguments.push(argument);
+    }
+
+    RustArguments {
+        parameters,
+        injected_parameters,
+        body_properties,
+        body_type,
+        body_required,
+        body_encoding,
+        body_media_type: media.map(|(_, media_type, _)| media_type.to_string()),
+        nested_types: mapper.structs,
+        enum_types: mapper.enums,
+    }
+}
+
+/// Maps the file properties of a multipart or form body to strings.
+///
+/// Multipart files, and arrays of them, are read by the generated server and
+/// sent as file parts; forms cannot carry files, so they send the string as is.
+fn form_field_type(prop: &mut RustPropertyInfo, schema: &JsonValue, encoding: BodyEncoding) {
+    let schema = merge_all_of(schema);
+    let items = schema
+        .get("items")
+        .filter(|_| schema_types(&schema) == ["array"]);
+    let rust_type = match items {
+        Some(items) if is_file_schema(items) => "Vec<String>",
+        None if is_file_schema(&schema) => "String",
+        _ => return,
+    };
+    prop.rust_type = rust_type.to_string();
+    if encoding == BodyEncoding::Multipart {
+        prop.file = true;
+        prop.description = Some(match prop.description.take() {
+            Some(description) => {
+                format!("{} ({FILE_ARGUMENT_DESCRIPTION})", description.trim_end())
+            }
+            None => FILE_ARGUMENT_DESCRIPTION.to_string(),
+        });
+    }
+}
+
+/// Maps a parameter's `in` to its kind, treating unknown locations as query
+fn parameter_kind(location: &str) -> ParameterKind {
+    match location {
+        "path" => ParameterKind::Path,
+        "header" => ParameterKind::Header,
+        "cookie" => ParameterKind::Cookie,
+        _ => ParameterKind::Query,
+    }
+}
+
+/// Serialization style of a parameter, defaulted by its location
+fn parameter_style(parameter: &OpenApiParameter) -> String {
+    match (&parameter.style, parameter.in_.as_str()) {
+        (Some(style), _) => style.clone(),
+        (None, "path" | "header") => "simple".to_string(),
+        (None, _) => "form".to_string(),
+    }
+}
+
+/// Renders a fixed value the way the generated `query_value` renders
+/// arguments: strings as-is, arrays comma-separated without their null items
+/// and anything else as JSON
+fn wire_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Array(items) => items
+            .iter()
+            .filter(|item| !item.is_null())
+            .map(wire_value)
+            .collect::<Vec<_>>()
+            .join(","),
+        other => other.to_string(),
+    }
+}
+
+/// Annotations of the tool, keyed by their `#[tool]` attribute name. The
+/// HTTP method gives the defaults: safe methods are read-only, DELETE is
+/// destructive and PUT and DELETE are idempotent. Hints set in `x-mcp` win.
+fn tool_annotations(op: &OpenApiOperation) -> BTreeMap<String, JsonValue> {
+    let mut hints = BTreeMap::new();
+    match op.method {
+        HttpMethod::Get | HttpMethod::Head | HttpMethod::Options => {
+            hints.insert("read_only_hint".to_string(), JsonValue::Bool(true));
         }
+        HttpMethod::Delete => {
+            hints.insert("destructive_hint".to_string(), JsonValue::Bool(true));
+            hints.insert("idempotent_hint".to_string(), JsonValue::Bool(true));
+        }
+        HttpMethod::Put => {
+            hints.insert("idempotent_hint".to_string(), JsonValue::Bool(true));
+        }
+        HttpMethod::Post | HttpMethod::Patch => {}
+    }
+    let Some(annotations) = op.mcp.annotations.as_ref() else {
+        return hints;
+    };
+    let overrides = [
+        ("title", annotations.title.clone().map(JsonValue::from)),
+        ("read_only_hint", annotations.read_only.map(JsonValue::from)),
+        (
+            "destructive_hint",
+            annotations.destructive.map(JsonValue::from),
+        ),
+        (
+            "idempotent_hint",
+            annotations.idempotent.map(JsonValue::from),
+        ),
+        (
+            "open_world_hint",
+            annotations.open_world.map(JsonValue::from),
+        ),
+    ];
+    for (key, value) in overrides
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+    {
+        hints.insert(key.to_string(), value);
+    }
+    hints
+}
+
+/// Description of the tool: the `x-mcp` description, or else the summary,
+/// description and first tag of the operation, or else its method and path.
+/// Control characters other than line breaks and tabs are dropped, as
+/// generated string literals cannot contain them.
+fn tool_description(op: &OpenApiOperation) -> String {
+    let description = match &op.mcp.description {
+        Some(description) => description.trim().to_string(),
+        None => [
+            op.summary.as_deref(),
+            op.description.as_deref(),
+            op.tags.iter().flatten().next().map(String::as_str),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" - "),
+    };
+    let description: String = description
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect();
+    if description.is_empty() {
+        format!("{} {}", op.method.as_str().to_uppercase(), op.path)
     } else {
-        "String".to_string()
+        description
+    }
+}
+
+/// Discriminator value of an alternative: recorded while inlining its `$ref`,
+/// or a single `const`/`enum` value of the discriminator property
+fn discriminator_value(alternative: &JsonValue, tag: &str) -> Option<String> {
+    if let Some(value) = alternative
+        .get("x-discriminator-value")
+        .and_then(JsonValue::as_str)
+    {
+        return Some(value.to_string());
+    }
+    let property = alternative.get("properties")?.get(tag)?;
+    match (property.get("const"), property.get("enum")) {
+        (Some(JsonValue::String(value)), _) => Some(value.clone()),
+        (None, Some(JsonValue::Array(values))) if values.len() == 1 => {
+            values[0].as_str().map(String::from)
+        }
+        _ => None,
+    }
+}
+
+/// PascalCase variant name from the discriminator value, the title or the type
+fn variant_name(alternative: &JsonValue, wire_name: Option<&str>, index: usize) -> String {
+    wire_name
+        .or_else(|| alternative.get("title").and_then(JsonValue::as_str))
+        .or_else(|| schema_types(alternative).first().copied())
+        .map(|name| to_proper_case(&identifier_chars(name)))
+        .filter(|name| is_type_name(name))
+        .unwrap_or_else(|| format!("Variant{}", index + 1))
+}
+
+/// Describes one schema property as a field of a generated type
+fn property_info(name: &str, schema: &JsonValue, rust_type: String) -> RustPropertyInfo {
+    RustPropertyInfo {
+        name: rust_field_name(name),
+        wire_name: name.to_string(),
+        argument_name: name.to_string(),
+        rust_type,
+        title: schema
+            .get("title")
+            .and_then(JsonValue::as_str)
+            .map(String::from),
+        description: schema_description(schema),
+        example: schema_example(schema),
+        required: false,
+        file: false,
     }
 }
 
@@ -177,14 +1261,12 @@ fn map_openapi_schema_to_rust_type(schema: Option<&JsonValue>) -> String {
 fn extract_envelope_properties(op: &OpenApiOperation) -> JsonValue {
     // Look for successful response (200, 201, etc.)
     for (status_code, response) in &op.responses {
-        if status_code.starts_with('2') {
-            if let Some(content) = response.content.as_ref() {
-                if let Some(json_content) = content.get("application/json") {
-                    if let Some(schema) = json_content.get("schema") {
-                        return extract_schema_envelope_properties(schema);
-                    }
-                }
-            }
+        if status_code.starts_with('2')
+            && let Some(content) = response.content.as_ref()
+            && let Some(json_content) = content.get("application/json")
+            && let Some(schema) = json_content.schema.as_ref()
+        {
+            return extract_schema_envelope_properties(schema);
         }
     }
     serde_json::json!({})
@@ -196,14 +1278,12 @@ fn extract_response_properties(op: &OpenApiOperation) -> Vec<RustPropertyInfo> {
 
     // Look for successful response (200, 201, etc.)
     for (status_code, response) in &op.responses {
-        if status_code.starts_with('2') {
-            if let Some(content) = response.content.as_ref() {
-                if let Some(json_content) = content.get("application/json") {
-                    if let Some(schema) = json_content.get("schema") {
-                        properties.extend(extract_schema_properties_as_rust(schema));
-                    }
-                }
-            }
+        if status_code.starts_with('2')
+            && let Some(content) = response.content.as_ref()
+            && let Some(json_content) = content.get("application/json")
+            && let Some(schema) = json_content.schema.as_ref()
+        {
+            properties.extend(extract_schema_properties_as_rust(schema));
         }
     }
 
@@ -220,6 +1300,8 @@ fn extract_handler_properties(op: &OpenApiOperation) -> Vec<String> {
 
 /// Helper to extract envelope properties from a schema
 fn extract_schema_envelope_properties(schema: &JsonValue) -> JsonValue {
+    let schema = &merge_all_of(schema);
+
     // Handle $ref references
     if let Some(_ref_str) = schema.get("$ref").and_then(JsonValue::as_str) {
         // For now, return empty object for $ref schemas
@@ -233,10 +1315,10 @@ fn extract_schema_envelope_properties(schema: &JsonValue) -> JsonValue {
     }
 
     // Handle array responses
-    if schema.get("type").and_then(JsonValue::as_str) == Some("array") {
-        if let Some(items) = schema.get("items") {
-            return extract_schema_envelope_properties(items);
-        }
+    if schema.get("type").and_then(JsonValue::as_str) == Some("array")
+        && let Some(items) = schema.get("items")
+    {
+        return extract_schema_envelope_properties(items);
     }
 
     serde_json::json!({})
@@ -245,6 +1327,7 @@ fn extract_schema_envelope_properties(schema: &JsonValue) -> JsonValue {
 /// Helper to extract schema properties and convert to RustPropertyInfo
 fn extract_schema_properties_as_rust(schema: &JsonValue) -> Vec<RustPropertyInfo> {
     let mut rust_properties = Vec::new();
+    let schema = &merge_all_of(schema);
 
     // Handle $ref references
     if let Some(_ref_str) = schema.get("$ref").and_then(JsonValue::as_str) {
@@ -254,34 +1337,13 @@ fn extract_schema_properties_as_rust(schema: &JsonValue) -> Vec<RustPropertyInfo
     }
 
     // Handle direct properties
-    if let Some(properties) = schema.get("properties").and_then(JsonValue::as_object) {
-        for (prop_name, prop_schema) in properties {
-            let rust_type = map_openapi_schema_to_rust_type(Some(prop_schema));
-            let title = prop_schema
-                .get("title")
-                .and_then(JsonValue::as_str)
-                .map(String::from);
-            let description = prop_schema
-                .get("description")
-                .and_then(JsonValue::as_str)
-                .map(String::from);
-            let example = prop_schema.get("example").cloned();
-
-            rust_properties.push(RustPropertyInfo {
-                name: to_snake_case(prop_name),
-                rust_type,
-                title,
-                description,
-                example,
-            });
-        }
-    }
+    rust_properties.extend(RustTypeMapper::default().properties(schema, ""));
 
     // Handle array responses - extract properties from items
-    if schema.get("type").and_then(JsonValue::as_str) == Some("array") {
-        if let Some(items) = schema.get("items") {
-            rust_properties.extend(extract_schema_properties_as_rust(items));
-        }
+    if schema.get("type").and_then(JsonValue::as_str) == Some("array")
+        && let Some(items) = schema.get("items")
+    {
+        rust_properties.extend(extract_schema_properties_as_rust(items));
     }
 
     rust_properties
@@ -291,16 +1353,13 @@ fn extract_schema_properties_as_rust(schema: &JsonValue) -> Vec<RustPropertyInfo
 fn extract_properties_schema(op: &OpenApiOperation) -> JsonMap<String, JsonValue> {
     // Look for successful response (200, 201, etc.)
     for (status_code, response) in &op.responses {
-        if status_code.starts_with('2') {
-            if let Some(content) = response.content.as_ref() {
-                if let Some(json_content) = content.get("application/json") {
-                    if let Some(schema) = json_content.get("schema") {
-                        if let Some(properties) = extract_schema_properties_map(schema) {
-                            return properties;
-                        }
-                    }
-                }
-            }
+        if status_code.starts_with('2')
+            && let Some(content) = response.content.as_ref()
+            && let Some(json_content) = content.get("application/json")
+            && let Some(schema) = json_content.schema.as_ref()
+            && let Some(properties) = extract_schema_properties_map(schema)
+        {
+            return properties;
         }
     }
     JsonMap::new()
@@ -310,17 +1369,234 @@ fn extract_properties_schema(op: &OpenApiOperation) -> JsonMap<String, JsonValue
 fn extract_response_schema(op: &OpenApiOperation) -> JsonValue {
     // Look for successful response (200, 201, etc.)
     for (status_code, response) in &op.responses {
-        if status_code.starts_with('2') {
-            if let Some(content) = response.content.as_ref() {
-                if let Some(json_content) = content.get("application/json") {
-                    if let Some(schema) = json_content.get("schema") {
-                        return schema.clone();
-                    }
+        if status_code.starts_with('2')
+            && let Some(content) = response.content.as_ref()
+            && let Some(json_content) = content.get("application/json")
+            && let Some(schema) = json_content.schema.as_ref()
+        {
+            return schema.clone();
+        }
+    }
+    serde_json::json!({})
+}
+
+/// Maps each documented 2xx status code of an operation to a response variant.
+///
+/// JSON bodies get their mapped type, without generating structs, so a body
+/// that deviates from the spec only fails the variant it was meant for. Ranges
+/// such as `2XX` and `default` are left to the enum's catch-all variant.
+fn extract_success_responses(op: &OpenApiOperation) -> Vec<RustResponseInfo> {
+    let mut mapper = RustTypeMapper::default();
+    let mut responses: Vec<RustResponseInfo> = op
+        .responses
+        .iter()
+        .filter(|(status, _)| status.len() == 3 && status.starts_with('2'))
+        .filter_map(|(status, response)| {
+            let code: u16 = status.parse().ok()?;
+            let json = response.content.as_ref().and_then(json_media);
+            let rust_type = json.map(|media| match &media.schema {
+                // Recursive schemas would need generated structs
+                Some(schema) if schema.get("$defs").is_none() => {
+                    mapper.rust_type(&merge_all_of(schema), "")
                 }
+                _ => "serde_json::Value".to_string(),
+            });
+            Some(RustResponseInfo {
+                status: status.clone(),
+                variant: status_variant(code),
+                description: response
+                    .description
+                    .clone()
+                    .filter(|d| !d.trim().is_empty()),
+                rust_type,
+            })
+        })
+        .collect();
+    responses.sort_by(|a, b| a.status.cmp(&b.status));
+    responses
+}
+
+/// Structured output of an operation whose documented 2xx responses all have
+/// the same JSON schema; with any other 2xx response the tool cannot promise
+/// its output. The schema is translated from OpenAPI to JSON Schema, and one
+/// that does not describe an object is wrapped in a `result` property, as
+/// MCP requires structured content to be an object.
+fn extract_output(op: &OpenApiOperation) -> Option<RustOutputInfo> {
+    let mut schemas = op
+        .responses
+        .iter()
+        .filter(|(status, _)| status.len() == 3 && status.starts_with('2'))
+        .map(|(_, response)| {
+            let media = response.content.as_ref().and_then(json_media)?;
+            media.schema.as_ref()
+        });
+    let schema = schemas.next()??;
+    if !schemas.all(|other| other == Some(schema)) {
+        return None;
+    }
+    let mut schema = output_json_schema(&merge_all_of(schema));
+    let is_object = schema.get("type") == Some(&json!("object"))
+        || (schema.get("type").is_none() && schema.get("properties").is_some());
+    if is_object {
+        return Some(RustOutputInfo {
+            schema,
+            wrapped: false,
+        });
+    }
+    // `#/$defs/` references resolve against the root, so the definitions move up
+    let defs = schema.as_object_mut().and_then(|obj| obj.remove("$defs"));
+    let mut wrapper = json!({
+        "type": "object",
+        "properties": {"result": schema},
+        "required": ["result"]
+    });
+    if let Some(defs) = defs {
+        wrapper["$defs"] = defs;
+    }
+    Some(RustOutputInfo {
+        schema: wrapper,
+        wrapped: true,
+    })
+}
+
+/// Resource of a GET operation, unless `x-mcp` opts it out, it has required
+/// arguments other than path parameters or a required parameter nothing fills
+/// in. Path arguments become URI variables; path segments holding an injected
+/// parameter are left out of the URI, as the server fills them in.
+fn extract_resource(op: &OpenApiOperation, arguments: &RustArguments) -> Option<RustResourceInfo> {
+    let needs_arguments = arguments.body_required
+        || arguments
+            .parameters
+            .iter()
+            .any(|p| p.required && p.kind != ParameterKind::Path)
+        || arguments
+            .injected_parameters
+            .iter()
+            .any(|p| p.required && p.config_key.is_none() && p.default_value.is_none());
+    if op.method != HttpMethod::Get || op.mcp.resource == Some(false) || needs_arguments {
+        return None;
+    }
+    let mut variables = Vec::new();
+    let mut segments = Vec::new();
+    'segments: for segment in op.path.split('/').filter(|s| !s.is_empty()) {
+        let mut uri_segment = String::new();
+        let mut rest = segment;
+        while let Some((literal, after)) = rest.split_once('{') {
+            let (name, after) = after.split_once('}')?;
+            uri_segment.push_str(literal);
+            rest = after;
+            let is_path_param = |kind: ParameterKind, wire_name: &str| {
+                kind == ParameterKind::Path && wire_name == name
+            };
+            if let Some(p) = arguments
+                .parameters
+                .iter()
+                .find(|p| is_path_param(p.kind, &p.wire_name))
+            {
+                let is_varname = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                let variable = if is_varname { name } else { &p.name };
+                uri_segment.push_str(&format!("{{{variable}}}"));
+                variables.push(RustUriVariable {
+                    name: variable.to_string(),
+                    field: p.name.clone(),
+                });
+            } else if arguments
+                .injected_parameters
+                .iter()
+                .any(|p| is_path_param(p.kind, &p.wire_name))
+            {
+                continue 'segments;
+            } else {
+                // A placeholder without a parameter cannot be filled in
+                return None;
             }
         }
+        uri_segment.push_str(rest);
+        segments.push(uri_segment);
     }
-    serde_json::json!({})
+    let fields = arguments.parameters.len()
+        + arguments.body_properties.len()
+        + usize::from(arguments.body_type.is_some());
+    let mut media_types = op
+        .responses
+        .iter()
+        .filter(|(status, _)| status.len() == 3 && status.starts_with('2'))
+        .filter_map(|(_, response)| response.content.as_ref())
+        .flat_map(|content| content.keys());
+    let mime_type = media_types.next().cloned();
+    let mime_type = mime_type.filter(|first| media_types.all(|other| other == first));
+    Some(RustResourceInfo {
+        uri_path: segments.join("/"),
+        template: !variables.is_empty(),
+        partial: fields > variables.len(),
+        variables,
+        mime_type,
+    })
+}
+
+/// Translates an OpenAPI schema to JSON Schema: `nullable` becomes a `null`
+/// type, and keywords JSON Schema validators do not know (`example`, `xml`,
+/// `discriminator`, `externalDocs`) are dropped.
+fn output_json_schema(schema: &JsonValue) -> JsonValue {
+    let JsonValue::Object(obj) = schema else {
+        return schema.clone();
+    };
+    let mut out = JsonMap::new();
+    for (key, value) in obj {
+        let value = match (key.as_str(), value) {
+            ("nullable" | "example" | "xml" | "discriminator" | "externalDocs", _) => continue,
+            ("properties" | "patternProperties" | "$defs", JsonValue::Object(schemas)) => {
+                JsonValue::Object(
+                    schemas
+                        .iter()
+                        .map(|(name, schema)| (name.clone(), output_json_schema(schema)))
+                        .collect(),
+                )
+            }
+            ("allOf" | "anyOf" | "oneOf" | "prefixItems", JsonValue::Array(schemas)) => {
+                JsonValue::Array(schemas.iter().map(output_json_schema).collect())
+            }
+            ("items" | "additionalProperties" | "not", value) => output_json_schema(value),
+            (_, value) => value.clone(),
+        };
+        out.insert(key.clone(), value);
+    }
+    if obj.get("nullable") != Some(&JsonValue::Bool(true)) {
+        return JsonValue::Object(out);
+    }
+    match out.get("type").cloned() {
+        Some(JsonValue::String(ty)) => {
+            out.insert("type".to_string(), json!([ty, "null"]));
+            JsonValue::Object(out)
+        }
+        Some(_) => JsonValue::Object(out),
+        None => json!({"anyOf": [out, {"type": "null"}]}),
+    }
+}
+
+/// Variant name of a 2xx status code, after its reason phrase where it has a common one
+fn status_variant(code: u16) -> String {
+    match code {
+        200 => "Ok".to_string(),
+        201 => "Created".to_string(),
+        202 => "Accepted".to_string(),
+        203 => "NonAuthoritativeInformation".to_string(),
+        204 => "NoContent".to_string(),
+        205 => "ResetContent".to_string(),
+        206 => "PartialContent".to_string(),
+        other => format!("Status{other}"),
+    }
+}
+
+/// The JSON media type of a body's `content`, preferring `application/json`
+fn json_media(
+    content: &std::collections::HashMap<String, OpenApiMediaType>,
+) -> Option<&OpenApiMediaType> {
+    content
+        .iter()
+        .filter(|(media_type, _)| BodyEncoding::of(media_type) == BodyEncoding::Json)
+        .min_by_key(|(media_type, _)| (*media_type != "application/json", *media_type))
+        .map(|(_, media)| media)
 }
 
 /// Extracts spec file name from operation (currently not available in operation context)
@@ -340,6 +1616,8 @@ fn extract_valid_fields(op: &OpenApiOperation) -> Vec<String> {
 
 /// Helper to extract properties as a JSON Map from a schema
 fn extract_schema_properties_map(schema: &JsonValue) -> Option<JsonMap<String, JsonValue>> {
+    let schema = &merge_all_of(schema);
+
     // Handle $ref references
     if let Some(_ref_str) = schema.get("$ref").and_then(JsonValue::as_str) {
         // For now, return None for $ref schemas
@@ -353,10 +1631,10 @@ fn extract_schema_properties_map(schema: &JsonValue) -> Option<JsonMap<String, J
     }
 
     // Handle array responses - extract properties from items
-    if schema.get("type").and_then(JsonValue::as_str) == Some("array") {
-        if let Some(items) = schema.get("items") {
-            return extract_schema_properties_map(items);
-        }
+    if schema.get("type").and_then(JsonValue::as_str) == Some("array")
+        && let Some(items) = schema.get("items")
+    {
+        return extract_schema_properties_map(items);
     }
 
     None
@@ -427,3 +1705,966 @@ impl LanguageContextBuilder for RustMcpClientContextBuilder {
         Ok(json!(normalized))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tool_names::{ToolNaming, assign_tool_names};
+    use serde_json::json;
+
+    fn operation(value: JsonValue) -> OpenApiOperation {
+        let mut op = serde_json::from_value(value).expect("test operation should deserialize");
+        assign_tool_names(std::slice::from_mut(&mut op), &ToolNaming::default()).unwrap();
+        op
+    }
+
+    #[test]
+    fn test_build_includes_http_method() {
+        let op = operation(json!({
+            "operationId": "addPet",
+            "path": "/pet",
+            "method": "post",
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["method"], "post");
+        assert_eq!(context["endpoint"], "add_pet");
+    }
+
+    #[test]
+    fn test_build_lists_security_requirements() {
+        let op = operation(json!({
+            "operationId": "addPet",
+            "path": "/pet",
+            "method": "post",
+            "security": [{"petstore_auth": ["write:pets"], "api_key": []}, {}],
+            "responses": {}
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(
+            context["security"],
+            json!([{"api_key": [], "petstore_auth": ["write:pets"]}, {}])
+        );
+
+        let op = operation(
+            json!({"operationId": "ping", "path": "/ping", "method": "get", "responses": {}}),
+        );
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["security"], json!([]));
+    }
+
+    #[test]
+    fn test_build_names_items_after_tool_name() {
+        let op = operation(json!({
+            "operationId": "type",
+            "path": "/types",
+            "method": "get",
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["tool_name"], "type");
+        assert_eq!(context["endpoint"], "type_");
+        assert_eq!(context["fn_name"], "type_");
+        assert_eq!(context["parameters_type"], "TypeParams");
+    }
+
+    #[test]
+    fn test_build_maps_object_body_to_arguments() {
+        let op = operation(json!({
+            "operationId": "updatePet",
+            "path": "/pet/{id}",
+            "method": "put",
+            "parameters": [{"name": "id", "in": "path", "schema": {"type": "integer"}}],
+            "requestBody": {"content": {"application/json": {"schema": {
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "photoUrls": {"type": "array", "items": {"type": "string"}},
+                    "category": {"type": "object", "properties": {"name": {"type": "string"}}}
+                }
+            }}}},
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        let props: Vec<(&str, &str, &str)> = context["body_properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| {
+                (
+                    p["name"].as_str().unwrap(),
+                    p["wire_name"].as_str().unwrap(),
+                    p["rust_type"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            props,
+            vec![
+                ("category", "category", "UpdatePetBodyCategory"),
+                ("body_id", "id", "i32"),
+                ("photo_urls", "photoUrls", "Vec<String>"),
+            ]
+        );
+        assert!(context["body_type"].is_null());
+        let category = &context["nested_types"][0];
+        assert_eq!(category["name"], "UpdatePetBodyCategory");
+        assert_eq!(category["properties"][0]["rust_type"], "String");
+        assert_eq!(context["body_properties"][1]["argument_name"], "body_id");
+        assert_eq!(context["body_properties"][2]["argument_name"], "photoUrls");
+    }
+
+    #[test]
+    fn test_build_generates_valid_field_names() {
+        let op = operation(json!({
+            "operationId": "searchItems",
+            "path": "/items/{type}",
+            "method": "post",
+            "parameters": [
+                {"name": "type", "in": "path", "schema": {"type": "string"}},
+                {"name": "X-Request-Id", "in": "header", "schema": {"type": "string"}},
+                {"name": "x_request_id", "in": "query", "schema": {"type": "string"}},
+                {"name": "filter[status]", "in": "query", "schema": {"type": "string", "enum": ["open"]}},
+                {"name": "2fa_code", "in": "query", "schema": {"type": "string"}},
+                {"name": "self", "in": "query", "schema": {"type": "string"}},
+                {"name": "body", "in": "query", "schema": {"type": "string"}}
+            ],
+            "requestBody": {"content": {"application/json": {"schema": {
+                "type": "array", "items": {"type": "string"}
+            }}}},
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        let names: Vec<(&str, &str, &str)> = context["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| {
+                (
+                    p["name"].as_str().unwrap(),
+                    p["wire_name"].as_str().unwrap(),
+                    p["argument_name"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("r#type", "type", "type"),
+                ("x_request_id", "X-Request-Id", "X-Request-Id"),
+                ("x_request_id_2", "x_request_id", "x_request_id"),
+                ("filter_status", "filter[status]", "filter[status]"),
+                ("_2fa_code", "2fa_code", "2fa_code"),
+                ("self_", "self", "self"),
+                // The request body already takes the `body` field and argument
+                ("body_2", "body", "body_2"),
+            ]
+        );
+        assert_eq!(context["enum_types"][0]["name"], "SearchItemsFilterStatus");
+
+        let op = operation(json!({
+            "operationId": "createItem",
+            "path": "/items/{type}",
+            "method": "post",
+            "parameters": [{"name": "type", "in": "path", "schema": {"type": "string"}}],
+            "requestBody": {"content": {"application/json": {"schema": {
+                "type": "object",
+                "properties": {
+                    "@type": {"type": "string"},
+                    "ref": {"type": "string"},
+                    "_links": {"type": "object"},
+                    "links": {"type": "object"}
+                }
+            }}}},
+            "responses": {}
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        let props: Vec<(&str, &str)> = context["body_properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| {
+                (
+                    p["name"].as_str().unwrap(),
+                    p["argument_name"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            props,
+            vec![
+                // Only the field clashes with the `type` parameter
+                ("body_type", "@type"),
+                ("links", "_links"),
+                ("links_2", "links"),
+                ("r#ref", "ref"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_marks_required_arguments() {
+        let body = json!({"content": {"application/json": {"schema": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {"type": "string"},
+                "owner": {"type": "object", "required": ["id"], "properties": {
+                    "id": {"type": "integer"},
+                    "email": {"type": "string"}
+                }}
+            }
+        }}}});
+        let mut op = operation(json!({
+            "operationId": "renamePet",
+            "path": "/pets/{petId}",
+            "method": "patch",
+            "parameters": [
+                {"name": "petId", "in": "path", "schema": {"type": "integer"}},
+                {"name": "dryRun", "in": "query", "schema": {"type": "boolean"}},
+                {"name": "reason", "in": "query", "required": true, "schema": {"type": "string"}}
+            ],
+            "requestBody": body,
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        let required = |list: &JsonValue| -> Vec<bool> {
+            list.as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["required"].as_bool().unwrap())
+                .collect()
+        };
+        // Path parameters are required even without `required: true`
+        assert_eq!(required(&context["parameters"]), vec![true, false, true]);
+        // An optional body makes all of its properties optional
+        assert_eq!(context["body_required"], false);
+        assert_eq!(required(&context["body_properties"]), vec![false, false]);
+        // Fields of generated types follow their own schema
+        assert_eq!(
+            required(&context["nested_types"][0]["properties"]),
+            vec![false, true]
+        );
+
+        op.request_body.as_mut().unwrap().required = Some(true);
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_required"], true);
+        assert_eq!(required(&context["body_properties"]), vec![true, false]);
+    }
+
+    #[test]
+    fn test_build_applies_mcp_extensions() {
+        let op = operation(json!({
+            "operationId": "listThings",
+            "path": "/tenants/{tenant}/things",
+            "method": "get",
+            "summary": "List things\u{7}",
+            "x-mcp": {
+                "name": "search_things",
+                "annotations": {"title": "Search things", "readOnly": true}
+            },
+            "parameters": [
+                {"name": "tenant", "in": "path", "x-mcp": {"config": "tenant"}},
+                {"name": "limit", "in": "query", "required": true, "x-mcp": {"default": 20}},
+                {"name": "fields", "in": "query", "x-mcp": {"hidden": true, "default": ["id", "name"]}},
+                {"name": "debug", "in": "query", "x-mcp": {"hidden": true}}
+            ],
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["tool_name"], "search_things");
+        assert_eq!(context["tool_description"], "List things");
+        assert_eq!(
+            context["annotations"],
+            json!({"read_only_hint": true, "title": "Search things"})
+        );
+
+        // Only `limit` remains an argument, made optional by its default
+        let parameters = context["parameters"].as_array().unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters[0]["wire_name"], "limit");
+        assert_eq!(parameters[0]["required"], false);
+        assert_eq!(parameters[0]["default_value"], "20");
+
+        // Hidden parameters without a value are not sent at all
+        assert_eq!(
+            context["injected_parameters"],
+            json!([
+                {"wire_name": "tenant", "kind": "path", "config_key": "tenant", "default_value": null, "required": true},
+                {"wire_name": "fields", "kind": "query", "config_key": null, "default_value": "id,name", "required": false}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_build_resolves_parameter_styles() {
+        let op = operation(json!({
+            "operationId": "listThings",
+            "path": "/things/{ids}",
+            "method": "get",
+            "parameters": [
+                {"name": "ids", "in": "path", "required": true, "schema": {"type": "array", "items": {"type": "integer"}}},
+                {"name": "tags", "in": "query", "schema": {"type": "array", "items": {"type": "string"}}},
+                {"name": "kinds", "in": "query", "style": "pipeDelimited", "explode": false, "schema": {"type": "array", "items": {"type": "string"}}},
+                {"name": "X-Ids", "in": "header", "schema": {"type": "array", "items": {"type": "string"}}}
+            ],
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        let styles: Vec<_> = context["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| {
+                (
+                    p["style"].as_str().unwrap(),
+                    p["explode"].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            styles,
+            vec![
+                ("simple", false),
+                ("form", true),
+                ("pipeDelimited", false),
+                ("simple", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wire_value_leaves_out_null_items() {
+        assert_eq!(wire_value(&json!("id")), "id");
+        assert_eq!(wire_value(&json!(20)), "20");
+        assert_eq!(wire_value(&json!(["id", null, "name"])), "id,name");
+        assert_eq!(wire_value(&json!([[1, null], 2])), "1,2");
+    }
+
+    #[test]
+    fn test_build_generates_types_for_inline_schemas() {
+        let op = operation(json!({
+            "operationId": "findPets",
+            "path": "/pets",
+            "method": "post",
+            "parameters": [
+                {"name": "status", "in": "query", "schema": {
+                    "type": "array",
+                    "items": {"type": "string", "enum": ["available", "sold-out", "1st"]}
+                }},
+                {"name": "params", "in": "query", "schema": {
+                    "type": "object", "properties": {"id": {"type": "string", "format": "uuid"}}
+                }}
+            ],
+            "requestBody": {"content": {"application/json": {"schema": {
+                "type": "object",
+                "properties": {
+                    "labels": {"type": "object", "additionalProperties": {
+                        "type": "object", "properties": {"since": {"type": "string", "format": "date"}}
+                    }}
+                }
+            }}}},
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(
+            context["parameters"][0]["target_type"],
+            "Vec<FindPetsStatusItem>"
+        );
+        // A generated type never takes the name of the parameters struct
+        assert_eq!(context["parameters"][1]["target_type"], "FindPetsParams2");
+        let status = &context["enum_types"][0];
+        assert_eq!(status["kind"], "strings");
+        let variants: Vec<(&str, &str)> = status["variants"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| {
+                (
+                    v["name"].as_str().unwrap(),
+                    v["wire_name"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            variants,
+            vec![
+                ("Available", "available"),
+                ("SoldOut", "sold-out"),
+                ("Value3", "1st"),
+            ]
+        );
+
+        assert_eq!(
+            context["body_properties"][0]["rust_type"],
+            "std::collections::HashMap<String, FindPetsBodyLabelsValue>"
+        );
+        let structs: Vec<(&str, &str)> = context["nested_types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| {
+                (
+                    s["name"].as_str().unwrap(),
+                    s["properties"][0]["rust_type"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            structs,
+            vec![
+                ("FindPetsParams2", "uuid::Uuid"),
+                ("FindPetsBodyLabelsValue", "chrono::NaiveDate"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_passes_non_object_body_through() {
+        let op = operation(json!({
+            "operationId": "createUsersWithList",
+            "path": "/user/createWithList",
+            "method": "post",
+            "requestBody": {"content": {"application/json": {"schema": {
+                "type": "array",
+                "items": {"type": "object", "properties": {"username": {"type": "string"}}}
+            }}}},
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_type"], "Vec<CreateUsersWithListBodyItem>");
+        assert_eq!(
+            context["nested_types"][0]["name"],
+            "CreateUsersWithListBodyItem"
+        );
+        assert_eq!(context["body_properties"], json!([]));
+
+        let op = operation(json!({
+            "operationId": "uploadFile",
+            "path": "/pet/{petId}/uploadImage",
+            "method": "post",
+            "requestBody": {"content": {"application/octet-stream": {}}},
+            "responses": {}
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_type"], "String");
+        assert_eq!(context["body_encoding"], "binary");
+        assert_eq!(context["body_media_type"], "application/octet-stream");
+    }
+
+    #[test]
+    fn test_build_chooses_body_encoding_by_media_type() {
+        let op = operation(json!({
+            "operationId": "uploadPhoto",
+            "path": "/pets/{petId}/photos",
+            "method": "post",
+            "requestBody": {"required": true, "content": {
+                "application/x-www-form-urlencoded": {"schema": {"type": "object"}},
+                "multipart/form-data": {"schema": {
+                    "type": "object",
+                    "required": ["photo"],
+                    "properties": {
+                        "caption": {"type": "string"},
+                        "photo": {"type": "string", "format": "binary", "description": "The photo."},
+                        "thumbnails": {"type": "array", "items": {"contentMediaType": "image/png"}}
+                    }
+                }}
+            }},
+            "responses": {}
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_encoding"], "multipart");
+        assert_eq!(context["body_media_type"], "multipart/form-data");
+        let fields: Vec<(&str, &str, bool)> = context["body_properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|prop| {
+                (
+                    prop["name"].as_str().unwrap(),
+                    prop["rust_type"].as_str().unwrap(),
+                    prop["file"].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("caption", "String", false),
+                ("photo", "String", true),
+                ("thumbnails", "Vec<String>", true)
+            ]
+        );
+        assert!(
+            context["body_properties"][1]["description"]
+                .as_str()
+                .unwrap()
+                .starts_with("The photo. (File contents as base64")
+        );
+
+        // JSON wins over every other media type, and text is sent as a string
+        let mut op = op;
+        let body = op.request_body.as_mut().unwrap();
+        body.content.insert(
+            "application/json".to_string(),
+            serde_json::from_value(json!({"schema": {"type": "object", "properties": {
+                "photo": {"type": "string", "format": "binary"}
+            }}}))
+            .unwrap(),
+        );
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_encoding"], "json");
+        assert_eq!(context["body_properties"][0]["rust_type"], "Vec<u8>");
+        assert_eq!(context["body_properties"][0]["file"], false);
+
+        let body = op.request_body.as_mut().unwrap();
+        body.content = serde_json::from_value(json!({"text/plain; charset=utf-8": {}})).unwrap();
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_encoding"], "text");
+        assert_eq!(context["body_type"], "String");
+    }
+
+    #[test]
+    fn test_build_maps_success_responses_by_status() {
+        let op = operation(json!({
+            "operationId": "importPets",
+            "path": "/pets/import",
+            "method": "post",
+            "responses": {
+                "202": {"description": "Queued", "content": {"application/json": {"schema": {
+                    "type": "object", "properties": {"job": {"type": "string"}}
+                }}}},
+                "200": {"description": "Imported", "content": {
+                    "application/xml": {"schema": {"type": "string"}},
+                    "application/problem+json": {"schema": {"type": "array", "items": {"type": "integer", "format": "int64"}}}
+                }},
+                "207": {"description": "", "content": {"text/csv": {}}},
+                "204": {"description": "Nothing to import"},
+                "2XX": {"description": "Other success"},
+                "400": {"description": "Bad request"}
+            }
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        let responses: Vec<(&str, &str, &JsonValue)> = context["success_responses"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                (
+                    r["status"].as_str().unwrap(),
+                    r["variant"].as_str().unwrap(),
+                    &r["rust_type"],
+                )
+            })
+            .collect();
+        assert_eq!(
+            responses,
+            [
+                ("200", "Ok", &json!("Vec<i64>")),
+                ("202", "Accepted", &json!("serde_json::Value")),
+                ("204", "NoContent", &JsonValue::Null),
+                ("207", "Status207", &JsonValue::Null)
+            ]
+        );
+        assert_eq!(context["success_responses"][0]["description"], "Imported");
+        assert!(context["success_responses"][3]["description"].is_null());
+        // The responses differ, so the tool has no output schema
+        assert!(context["output"].is_null());
+    }
+
+    #[test]
+    fn test_build_output_schema_from_success_responses() {
+        let op = operation(json!({
+            "operationId": "getPet",
+            "path": "/pets/{id}",
+            "method": "get",
+            "responses": {
+                "200": {"description": "A pet", "content": {"application/json": {"schema": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": {"type": "string", "example": "Rex"},
+                        "tag": {"type": "string", "nullable": true},
+                        "example": {"type": "integer"}
+                    },
+                    "xml": {"name": "pet"}
+                }}}},
+                "404": {"description": "Not found"}
+            }
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["output"]["wrapped"], false);
+        assert_eq!(
+            context["output"]["schema"],
+            json!({
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string"},
+                    "tag": {"type": ["string", "null"]},
+                    "example": {"type": "integer"}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_wraps_output_schema_that_is_not_an_object() {
+        let op = operation(json!({
+            "operationId": "listUnits",
+            "path": "/units",
+            "method": "get",
+            "responses": {
+                "200": {"content": {"application/json": {"schema": {
+                    "type": "array",
+                    "items": {"$ref": "#/$defs/Unit"},
+                    "$defs": {"Unit": {"type": "object", "properties": {
+                        "parent": {"$ref": "#/$defs/Unit"}
+                    }}}
+                }}}}
+            }
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["output"]["wrapped"], true);
+        let schema = &context["output"]["schema"];
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["result"]));
+        assert_eq!(
+            schema["properties"]["result"],
+            json!({"type": "array", "items": {"$ref": "#/$defs/Unit"}})
+        );
+        assert!(schema["$defs"]["Unit"].is_object());
+
+        // Without a JSON body there is nothing to describe
+        let op = operation(json!({
+            "operationId": "deleteUnit",
+            "path": "/units/{id}",
+            "method": "delete",
+            "responses": {"204": {"description": "Deleted"}}
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert!(context["output"].is_null());
+    }
+
+    #[test]
+    fn test_build_derives_annotations_from_http_method() {
+        let annotations = |method: &str, mcp: JsonValue| {
+            let op = operation(json!({
+                "operationId": "thing",
+                "path": "/things/{id}",
+                "method": method,
+                "x-mcp": mcp,
+                "responses": {}
+            }));
+            RustEndpointContextBuilder.build(&op).unwrap()["annotations"].clone()
+        };
+        assert_eq!(
+            annotations("get", json!({})),
+            json!({"read_only_hint": true})
+        );
+        assert_eq!(
+            annotations("delete", json!({})),
+            json!({"destructive_hint": true, "idempotent_hint": true})
+        );
+        assert_eq!(
+            annotations("put", json!({})),
+            json!({"idempotent_hint": true})
+        );
+        assert_eq!(annotations("post", json!({})), json!({}));
+        // `x-mcp` annotations override the defaults
+        assert_eq!(
+            annotations("delete", json!({"annotations": {"destructive": false}})),
+            json!({"destructive_hint": false, "idempotent_hint": true})
+        );
+    }
+
+    #[test]
+    fn test_build_exposes_get_operations_as_resources() {
+        let resource = |path: &str, method: &str, parameters: JsonValue, mcp: JsonValue| {
+            let op = operation(json!({
+                "operationId": "thing",
+                "path": path,
+                "method": method,
+                "parameters": parameters,
+                "x-mcp": mcp,
+                "responses": {"200": {"content": {"application/json": {"schema": {"type": "object"}}}}}
+            }));
+            RustEndpointContextBuilder.build(&op).unwrap()["resource"].clone()
+        };
+        let limit = json!({"name": "limit", "in": "query", "schema": {"type": "integer"}});
+        assert_eq!(
+            resource("/things", "get", json!([limit]), json!({})),
+            json!({
+                "uri_path": "things",
+                "template": false,
+                "variables": [],
+                "partial": true,
+                "mime_type": "application/json"
+            })
+        );
+
+        // Path arguments become URI variables; names RFC 6570 rejects give way to the field name
+        let path_param = |name: &str, mcp: JsonValue| json!({"name": name, "in": "path", "required": true, "schema": {"type": "string"}, "x-mcp": mcp});
+        let template = resource(
+            "/things/{thing-id}/parts/{partId}.json",
+            "get",
+            json!([
+                path_param("thing-id", json!({})),
+                path_param("partId", json!({}))
+            ]),
+            json!({}),
+        );
+        assert_eq!(
+            template["uri_path"],
+            "things/{thing_id}/parts/{partId}.json"
+        );
+        assert_eq!(template["template"], true);
+        assert_eq!(
+            template["variables"],
+            json!([
+                {"name": "thing_id", "field": "thing_id"},
+                {"name": "partId", "field": "part_id"}
+            ])
+        );
+        assert_eq!(template["partial"], false);
+
+        // Segments the server fills in are left out of the URI
+        let injected = resource(
+            "/tenants/{tenant}/things",
+            "get",
+            json!([path_param("tenant", json!({"config": "tenant"}))]),
+            json!({}),
+        );
+        assert_eq!(injected["uri_path"], "tenants/things");
+
+        let required =
+            json!({"name": "q", "in": "query", "required": true, "schema": {"type": "string"}});
+        assert!(resource("/things", "get", json!([required]), json!({})).is_null());
+        assert!(resource("/things", "get", json!([]), json!({"resource": false})).is_null());
+        assert!(resource("/things", "post", json!([]), json!({"resource": true})).is_null());
+    }
+
+    #[test]
+    fn test_build_boxes_recursive_body_types() {
+        let op = operation(json!({
+            "operationId": "createUnit",
+            "path": "/units",
+            "method": "post",
+            "requestBody": {"content": {"application/json": {"schema": {
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string"},
+                    "parent": {"$ref": "#/$defs/Unit"}
+                },
+                "$defs": {"Unit": {
+                    "type": "object",
+                    "description": "An org-chart unit",
+                    "properties": {
+                        "parent": {"$ref": "#/$defs/Unit"},
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/Unit"}}
+                    }
+                }}
+            }}}},
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_properties"][1]["rust_type"], "Box<Unit>");
+
+        let unit = &context["nested_types"][0];
+        assert_eq!(unit["name"], "Unit");
+        assert_eq!(unit["description"], "An org-chart unit");
+        assert_eq!(unit["properties"][0]["rust_type"], "Vec<Unit>");
+        assert_eq!(unit["properties"][1]["rust_type"], "Box<Unit>");
+    }
+
+    #[test]
+    fn test_build_merges_all_of_and_maps_unions_to_enums() {
+        let op = operation(json!({
+            "operationId": "addAnimal",
+            "path": "/animals",
+            "method": "post",
+            "requestBody": {"content": {"application/json": {"schema": {
+                "oneOf": [
+                    {
+                        "x-discriminator-value": "cat",
+                        "allOf": [
+                            {"type": "object", "properties": {"petType": {"type": "string"}}},
+                            {"properties": {"lives": {"type": "integer"}}}
+                        ]
+                    },
+                    {"type": "object", "properties": {
+                        "petType": {"const": "dog"},
+                        "owner": {"anyOf": [{"type": "string"}, {"type": "null"}]}
+                    }},
+                    {"type": "null"}
+                ],
+                "discriminator": {"propertyName": "petType"}
+            }}}},
+            "responses": {}
+        }));
+
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert_eq!(context["body_type"], "AddAnimalBody");
+        let body = &context["enum_types"][0];
+        assert_eq!(body["name"], "AddAnimalBody");
+        assert_eq!(body["tag"], "petType");
+        let variants = body["variants"].as_array().unwrap();
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0]["name"], "Cat");
+        assert_eq!(variants[0]["wire_name"], "cat");
+        // The discriminator is consumed by serde, so it is not a field
+        assert_eq!(variants[0]["properties"][0]["name"], "lives");
+        assert_eq!(variants[0]["properties"].as_array().unwrap().len(), 1);
+        assert_eq!(variants[1]["wire_name"], "dog");
+        assert_eq!(variants[1]["properties"][0]["rust_type"], "String");
+
+        let op = operation(json!({
+            "operationId": "createPet",
+            "path": "/pets",
+            "method": "post",
+            "requestBody": {"content": {"application/json": {"schema": {"allOf": [
+                {"type": "object", "properties": {"name": {"type": "string"}}},
+                {"properties": {"owner": {"oneOf": [
+                    {"type": "string"},
+                    {"type": "object", "title": "person", "properties": {"id": {"type": "integer"}}}
+                ]}}}
+            ]}}}},
+            "responses": {}
+        }));
+        let context = RustEndpointContextBuilder.build(&op).unwrap();
+        assert!(context["body_type"].is_null());
+        assert_eq!(
+            context["body_properties"][1]["rust_type"],
+            "CreatePetBodyOwner"
+        );
+        let owner = &context["enum_types"][0];
+        assert!(owner["tag"].is_null());
+        assert_eq!(owner["variants"][0]["rust_type"], "String");
+        assert_eq!(owner["variants"][1]["name"], "Person");
+        assert!(owner["variants"][1]["rust_type"].is_null());
+    }
+
+    #[test]
+    fn test_map_json_schema_2020_12_types() {
+        let cases = [
+            (json!({"type": ["string", "null"]}), "String"),
+            (json!({"type": ["integer", "string"]}), "serde_json::Value"),
+            (json!({"type": "null"}), "serde_json::Value"),
+            (json!({"const": "dog"}), "String"),
+            (json!({"const": 3}), "i32"),
+            (json!({"const": true}), "bool"),
+            (
+                json!({"oneOf": [{"type": "integer"}, {"type": "null"}]}),
+                "i32",
+            ),
+            (json!({"allOf": [{"type": "boolean"}]}), "bool"),
+            (json!({"type": "integer", "format": "int64"}), "i64"),
+            (json!({"type": "integer", "format": "uint8"}), "u8"),
+            (json!({"type": "number", "format": "float"}), "f32"),
+            (
+                json!({"type": "string", "format": "date-time"}),
+                "chrono::DateTime<chrono::Utc>",
+            ),
+            (json!({"type": "string", "format": "uuid"}), "uuid::Uuid"),
+            (json!({"type": "string", "format": "byte"}), "String"),
+            (json!({"type": "string", "format": "binary"}), "Vec<u8>"),
+            (json!({"type": "string", "enum": ["a", "b"]}), "String"),
+            (json!({"type": "file"}), "serde_json::Value"),
+            (json!({"type": "array"}), "Vec<serde_json::Value>"),
+            (
+                json!({"type": "object", "additionalProperties": {"type": "integer"}}),
+                "std::collections::HashMap<String, i32>",
+            ),
+            (
+                json!({"type": "object", "additionalProperties": true}),
+                "serde_json::Value",
+            ),
+            (
+                json!({"type": "array", "items": {"type": ["integer", "null"]}}),
+                "Vec<Option<i32>>",
+            ),
+            (
+                json!({"type": "array", "prefixItems": [{"type": "number"}, {"type": "string"}], "items": false}),
+                "(f64, String)",
+            ),
+            (
+                json!({"type": "array", "prefixItems": [{"type": "number"}]}),
+                "(f64,)",
+            ),
+            (
+                json!({"type": "array", "prefixItems": [{"type": "number"}], "items": {"type": "string"}}),
+                "Vec<serde_json::Value>",
+            ),
+        ];
+        for (schema, expected) in cases {
+            assert_eq!(
+                RustTypeMapper::default().rust_type(&schema, ""),
+                expected,
+                "{schema}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_openapi_3_1_fixture() {
+        let spec = crate::core::openapi::OpenApiContext::from_file_or_url(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/openapi/petstore.openapi.v31.json"
+        ))
+        .await
+        .unwrap();
+        let mut ops = spec.parse_operations().await.unwrap();
+        assign_tool_names(&mut ops, &ToolNaming::default()).unwrap();
+        let create = ops.iter().find(|op| op.id == "createPet").unwrap();
+
+        let context = RustEndpointContextBuilder.build(create).unwrap();
+        let types: Vec<(&str, &str)> = context["body_properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| {
+                (
+                    p["name"].as_str().unwrap(),
+                    p["rust_type"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("age", "i32"),
+                ("kind", "String"),
+                ("location", "(f64, f64)"),
+                ("metadata", "serde_json::Value"),
+                ("name", "String"),
+                ("nickname", "String"),
+                ("scores", "Vec<Option<i32>>"),
+                ("tag", "CreatePetBodyTag"),
+            ]
+        );
+        assert_eq!(context["body_properties"][4]["example"], "Rex");
+        // The schema's own $defs were inlined, so only the inline object needs a struct
+        let nested: Vec<&str> = context["nested_types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(nested, vec!["CreatePetBodyTag"]);
+
+        let list = ops.iter().find(|op| op.id == "listPets").unwrap();
+        let context = RustEndpointContextBuilder.build(list).unwrap();
+        assert_eq!(context["parameters"][0]["target_type"], "i32");
+        assert_eq!(context["parameters"][0]["example"], 20);
+    }
+}
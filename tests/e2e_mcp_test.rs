@@ -197,12 +197,11 @@ async fn test_mcp_server_client_generation() -> Result<()> {
     // Ensure standalone crates by appending minimal workspace footer
     for path in [&server_output, &client_output] {
         let cargo_toml = path.join("Cargo.toml");
-        if let Ok(contents) = fs::read_to_string(&cargo_toml) {
-            if !contents.contains("[workspace]") {
-                if let Ok(mut f) = OpenOptions::new().append(true).open(&cargo_toml) {
-                    writeln!(f, "\n[workspace]\n").ok();
-                }
-            }
+        if let Ok(contents) = fs::read_to_string(&cargo_toml)
+            && !contents.contains("[workspace]")
+            && let Ok(mut f) = OpenOptions::new().append(true).open(&cargo_toml)
+        {
+            writeln!(f, "\n[workspace]\n").ok();
         }
     }
 
@@ -532,7 +531,7 @@ async fn test_mcp_with_interactive_client(
             in_resources_list = true;
         } else if in_resources_list && line.trim().starts_with("") && line.contains(":") {
             // Extract URI from lines like "  uri: description"
-            if let Some(uri) = line.trim().split(':').next() {
+            if let Some(uri) = line.trim().split(": ").next() {
                 let uri = uri.trim();
                 if !uri.is_empty() && !uri.contains("No resources") {
                     resource_uris.push(uri.to_string());
@@ -542,6 +541,15 @@ async fn test_mcp_with_interactive_client(
     }
 
     info!("Found {} resources to fetch", resource_uris.len());
+    // GET operations without required arguments are resources too
+    if !resource_uris
+        .iter()
+        .any(|uri| uri.ends_with("://store/inventory"))
+    {
+        return Err(anyhow::anyhow!(
+            "GET operations are not listed as resources: {resource_uris:?}"
+        ));
+    }
 
     // Get each resource to populate the cache
     for uri in &resource_uris {
@@ -1068,6 +1076,380 @@ fn test_cli_flag_combinations() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_validate_command_reports_issues() -> Result<()> {
+    let agenterra = env!("CARGO_BIN_EXE_agenterra");
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Use sandbox directory under target/tmp to avoid polluting repo root
+    let sandbox_dir = project_dir
+        .join(CLI_FLAG_TESTS_SANDBOX_DIR)
+        .join("test_validate_command_reports_issues");
+    let _ = std::fs::remove_dir_all(&sandbox_dir);
+    std::fs::create_dir_all(&sandbox_dir)?;
+
+    // A spec with a malformed parameter and two operations that collide once
+    // converted to tool names
+    let spec_path = sandbox_dir.join("colliding.json");
+    fs::write(
+        &spec_path,
+        serde_json::to_string(&serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Colliding", "version": "1.0"},
+            "servers": [{"url": "https://api.example.com"}],
+            "paths": {
+                "/a": {"get": {"operationId": "listItems", "responses": {"200": {
+                    "description": "ok",
+                    "content": {"application/json": {"schema": {"type": "array"}}}
+                }}}},
+                "/b": {"get": {
+                    "operationId": "list_items",
+                    "parameters": [{"name": "page", "in": "query", "required": "yes", "schema": {"type": "integer"}}],
+                    "responses": {"200": {
+                        "description": "ok",
+                        "content": {"application/json": {"schema": {"type": "array"}}}
+                    }}
+                }}
+            }
+        }))?,
+    )?;
+
+    let result = Command::new(agenterra)
+        .current_dir(&sandbox_dir)
+        .args(["validate", "--format", "json", "--schema-path"])
+        .arg(&spec_path)
+        .output()
+        .context("Failed to run agenterra validate")?;
+    assert_eq!(
+        result.status.code(),
+        Some(5),
+        "errors should fail validation"
+    );
+    let report: serde_json::Value = serde_json::from_slice(&result.stdout)
+        .context("validate --format json should print only JSON on stdout")?;
+    assert_eq!(report["errors"], 1);
+    assert_eq!(report["warnings"], 2);
+    let diagnostics = report["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics[0]["pointer"], "/paths/~1a/get");
+    assert!(
+        diagnostics[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("renamed to 'list_items_")
+    );
+    assert_eq!(diagnostics[2]["pointer"], "/paths/~1b/get/parameters/0");
+    assert_eq!(diagnostics[2]["severity"], "error");
+
+    // Warnings alone do not fail the command
+    let result = Command::new(agenterra)
+        .current_dir(&sandbox_dir)
+        .args(["validate", "--schema-path"])
+        .arg(project_dir.join("tests/fixtures/openapi/petstore.openapi.v3.json"))
+        .output()
+        .context("Failed to run agenterra validate")?;
+    assert!(result.status.success());
+    let output = String::from_utf8_lossy(&result.stdout);
+    assert!(output.contains("warning at #/servers/0/url"), "{output}");
+    assert!(output.contains("0 error(s)"), "{output}");
+    Ok(())
+}
+
+#[test]
+fn test_validate_command_reports_dangling_refs() -> Result<()> {
+    let agenterra = env!("CARGO_BIN_EXE_agenterra");
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_dir = project_dir
+        .join(CLI_FLAG_TESTS_SANDBOX_DIR)
+        .join("test_validate_command_reports_dangling_refs");
+    let _ = std::fs::remove_dir_all(&sandbox_dir);
+    std::fs::create_dir_all(&sandbox_dir)?;
+
+    // References the bundler cannot resolve are reported, not fatal
+    let spec_path = sandbox_dir.join("dangling.json");
+    fs::write(
+        &spec_path,
+        serde_json::to_string(&serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Dangling", "version": "1.0"},
+            "servers": [{"url": "https://api.example.com"}],
+            "paths": {
+                "/pets": {"get": {"operationId": "listPets", "responses": {"200": {
+                    "description": "ok",
+                    "content": {"application/json": {"schema": {
+                        "$ref": "#/components/schemas/Missing"
+                    }}}
+                }}}}
+            }
+        }))?,
+    )?;
+
+    let result = Command::new(agenterra)
+        .current_dir(&sandbox_dir)
+        .args(["validate", "--format", "json", "--schema-path"])
+        .arg(&spec_path)
+        .output()
+        .context("Failed to run agenterra validate")?;
+    assert_eq!(
+        result.status.code(),
+        Some(5),
+        "a dangling $ref should fail validation: {}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&result.stdout)
+        .context("validate --format json should print only JSON on stdout")?;
+    assert_eq!(report["errors"], 1, "{report:#}");
+    let diagnostics = report["diagnostics"].as_array().unwrap();
+    let dangling = diagnostics
+        .iter()
+        .find(|d| {
+            d["pointer"] == "/paths/~1pets/get/responses/200/content/application~1json/schema/$ref"
+        })
+        .unwrap_or_else(|| panic!("no diagnostic for the dangling $ref: {report:#}"));
+    assert_eq!(dangling["severity"], "error");
+    assert!(
+        dangling["message"]
+            .as_str()
+            .unwrap()
+            .contains("'#/components/schemas/Missing'")
+    );
+    Ok(())
+}
+
+/// Scaffold a server named `name` from `schema` with `extra_args`, then `cargo check` it.
+///
+/// The generated servers share one target directory so dependencies build once.
+fn scaffold_and_check_server(
+    name: &str,
+    schema: &std::path::Path,
+    extra_args: &[&str],
+) -> Result<std::path::PathBuf> {
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let scaffold_path = project_dir.join("target/tmp/e2e-check-tests");
+    let server_output = scaffold_path.join(name);
+    let _ = std::fs::remove_dir_all(&server_output);
+    std::fs::create_dir_all(&scaffold_path)?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_agenterra"))
+        .args(["scaffold", "mcp", "server", "--project-name", name])
+        .arg("--output-dir")
+        .arg(&scaffold_path)
+        .arg("--schema-path")
+        .arg(schema)
+        .arg("--template-dir")
+        .arg(project_dir.join("templates/mcp/server/rust_axum"))
+        .args(extra_args)
+        .output()
+        .with_context(|| format!("Failed to run agenterra for {name}"))?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to generate {}: {}",
+            name,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    info!("Checking {}...", name);
+    let check = Command::new("cargo")
+        .current_dir(&server_output)
+        .env("CARGO_TARGET_DIR", scaffold_path.join("target"))
+        .args(["check", "--all-targets"])
+        .output()
+        .with_context(|| format!("Failed to check {name}"))?;
+    if !check.status.success() {
+        return Err(anyhow::anyhow!(
+            "Generated server {} does not compile: {}",
+            name,
+            String::from_utf8_lossy(&check.stderr)
+        ));
+    }
+    Ok(server_output)
+}
+
+#[test]
+fn test_large_spec_generates_a_compiling_server() -> Result<()> {
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // int64 and uuid ids, date-times, binary uploads and downloads, enums and inline objects
+    let schema_path = project_dir.join("tests/fixtures/openapi/inventory.openapi.v3.json");
+    let server_output = scaffold_and_check_server("e2e_inventory_server", &schema_path, &[])?;
+
+    let handlers = server_output.join("src/handlers");
+    let get_item = fs::read_to_string(handlers.join("get_item.rs"))?;
+    assert!(get_item.contains("pub item_id: i64"), "{get_item}");
+    assert!(
+        get_item.contains("chrono::DateTime<chrono::Utc>"),
+        "{get_item}"
+    );
+    let list_items = fs::read_to_string(handlers.join("list_items.rs"))?;
+    assert!(
+        list_items.contains("pub enum ListItemsStatus"),
+        "{list_items}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_spec_descriptions_are_escaped_in_generated_code() -> Result<()> {
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_dir = project_dir
+        .join(CLI_FLAG_TESTS_SANDBOX_DIR)
+        .join("test_spec_descriptions_are_escaped_in_generated_code");
+    let _ = std::fs::remove_dir_all(&sandbox_dir);
+    std::fs::create_dir_all(&sandbox_dir)?;
+
+    // Descriptions end up in doc comments and attributes of the generated code
+    let spec_path = sandbox_dir.join("descriptions.json");
+    fs::write(
+        &spec_path,
+        serde_json::to_string(&serde_json::json!({
+            "openapi": "3.0.3",
+            "info": {"title": "Descriptions", "version": "1.0"},
+            "servers": [{"url": "https://api.example.com"}],
+            "paths": {
+                "/notes": {"post": {
+                    "operationId": "createNote",
+                    "parameters": [{
+                        "name": "format",
+                        "in": "query",
+                        "description": "the \"type\"# of thing",
+                        "schema": {"type": "string"}
+                    }],
+                    "requestBody": {"content": {"application/json": {"schema": {
+                        "type": "object",
+                        "properties": {
+                            "title": {"type": "string", "description": "the \"type\"# of thing"},
+                            "meta": {
+                                "type": "object",
+                                "description": "Meta\ndata",
+                                "properties": {
+                                    "kind": {
+                                        "type": "string",
+                                        "description": "multi\nline enum",
+                                        "enum": ["plain", "rich"]
+                                    },
+                                    "label": {"type": "string", "description": "the \"type\"# of thing"}
+                                }
+                            },
+                            "attachment": {"oneOf": [
+                                {"type": "object", "properties": {
+                                    "url": {"type": "string", "description": "the \"type\"# of thing"}
+                                }},
+                                {"type": "object", "properties": {"path": {"type": "string"}}}
+                            ]}
+                        }
+                    }}}},
+                    "responses": {"201": {
+                        "description": "the \"created\"# note",
+                        "content": {"application/json": {"schema": {
+                            "type": "object",
+                            "properties": {
+                                "id": {"type": "string", "description": "the \"type\"# of thing"}
+                            }
+                        }}}
+                    }}
+                }}
+            }
+        }))?,
+    )?;
+
+    let server_output = scaffold_and_check_server("e2e_descriptions_server", &spec_path, &[])?;
+    let handler = fs::read_to_string(server_output.join("src/handlers/create_note.rs"))?;
+    assert!(handler.contains(r#"#[doc = "Meta\ndata"]"#), "{handler}");
+    assert!(
+        handler.contains(r#"#[doc = "multi\nline enum"]"#),
+        "{handler}"
+    );
+    assert_eq!(
+        handler
+            .matches(r##"description = "the \"type\"# of thing")]"##)
+            .count(),
+        4,
+        "{handler}"
+    );
+    assert!(
+        handler.contains(r##"description = " - the \"type\"# of thing")]"##),
+        "{handler}"
+    );
+    assert!(
+        handler.contains(r##"#[doc = "201: the \"created\"# note"]"##),
+        "{handler}"
+    );
+    assert!(
+        handler.contains(r##"#[doc = " - `format` (String, optional): the \"type\"# of thing"]"##),
+        "{handler}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_scaffold_with_operation_selection() -> Result<()> {
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let schema_path = project_dir.join("tests/fixtures/openapi/petstore.openapi.v3.json");
+    let server_output = scaffold_and_check_server(
+        "e2e_selected_server",
+        &schema_path,
+        &[
+            "--include-tag",
+            "store",
+            "--exclude-method",
+            "delete",
+            "--base-url",
+            "https://petstore3.swagger.io",
+        ],
+    )?;
+
+    let mut handlers: Vec<String> = fs::read_dir(server_output.join("src/handlers"))?
+        .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+        .collect::<Result<_>>()?;
+    handlers.sort();
+    assert_eq!(
+        handlers,
+        [
+            "get_inventory.rs",
+            "get_order_by_id.rs",
+            "mod.rs",
+            "place_order.rs"
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_scaffold_rejects_an_empty_selection() -> Result<()> {
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sandbox_dir = project_dir
+        .join(CLI_FLAG_TESTS_SANDBOX_DIR)
+        .join("test_scaffold_rejects_an_empty_selection");
+    let _ = std::fs::remove_dir_all(&sandbox_dir);
+    std::fs::create_dir_all(&sandbox_dir)?;
+
+    let result = Command::new(env!("CARGO_BIN_EXE_agenterra"))
+        .current_dir(&sandbox_dir)
+        .args([
+            "scaffold",
+            "mcp",
+            "server",
+            "--project-name",
+            "empty_selection",
+        ])
+        .arg("--schema-path")
+        .arg(project_dir.join("tests/fixtures/openapi/petstore.openapi.v3.json"))
+        .arg("--template-dir")
+        .arg(project_dir.join("templates/mcp/server/rust_axum"))
+        .args(["--base-url", "https://petstore3.swagger.io"])
+        .args(["--include-tag", "store", "--include-method", "patch"])
+        .output()
+        .context("Failed to run agenterra")?;
+    assert!(
+        !result.status.success(),
+        "a selection without operations should fail"
+    );
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("None of the 19 operations match the include/exclude filters"),
+        "{stderr}"
+    );
+    Ok(())
+}
+
 /// Verify SQLite cache by directly querying the database
 fn verify_sqlite_cache(client_output: &std::path::Path) -> Result<()> {
     // The new unified database follows OS-specific paths, but for this E2E test,
@@ -1417,3 +1799,256 @@ async fn test_mcp_sse_transport() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_mcp_streamable_http_transport() -> Result<()> {
+    // Initialize tracing
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive("e2e_mcp_test=info".parse().unwrap())
+                .add_directive("agenterra=info".parse().unwrap()),
+        )
+        .with_test_writer()
+        .try_init();
+
+    info!("=== Testing MCP Streamable HTTP Transport ===");
+
+    // Skip test if Streamable HTTP testing is not enabled
+    if std::env::var("ENABLE_STREAMABLE_HTTP_TEST").is_err() {
+        info!("Skipping Streamable HTTP test - set ENABLE_STREAMABLE_HTTP_TEST=1 to run");
+        return Ok(());
+    }
+
+    let project_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let agenterra = project_dir
+        .join("target/debug/agenterra")
+        .to_string_lossy()
+        .into_owned();
+
+    let scaffold_path = project_dir.join("target/tmp/e2e-streamable-http-tests");
+    let _ = std::fs::remove_dir_all(&scaffold_path);
+    std::fs::create_dir_all(&scaffold_path)?;
+
+    let server_name = "e2e_http_server";
+    let client_name = "e2e_http_client";
+    cleanup_project_databases(client_name)?;
+
+    // Generate and build the server and the client
+    let schema_path = project_dir.join("tests/fixtures/openapi/petstore.openapi.v3.json");
+    let server_template_dir = project_dir.join("templates/mcp/server/rust_axum");
+    let client_template_dir = project_dir.join("templates/mcp/client/rust_reqwest");
+    let generations: [(&str, Vec<&str>); 2] = [
+        (
+            server_name,
+            vec![
+                "server",
+                "--schema-path",
+                schema_path.to_str().unwrap(),
+                "--template-dir",
+                server_template_dir.to_str().unwrap(),
+                "--base-url",
+                "https://petstore3.swagger.io",
+            ],
+        ),
+        (
+            client_name,
+            vec![
+                "client",
+                "--template-dir",
+                client_template_dir.to_str().unwrap(),
+            ],
+        ),
+    ];
+    for (name, args) in generations {
+        info!("Generating and building {}...", name);
+        let output = Command::new(&agenterra)
+            .args(["scaffold", "mcp"])
+            .args(&args)
+            .args(["--project-name", name])
+            .args(["--output-dir", scaffold_path.to_str().unwrap()])
+            .output()
+            .with_context(|| format!("Failed to run agenterra for {name}"))?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to generate {}: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let build_output = Command::new("cargo")
+            .current_dir(scaffold_path.join(name))
+            .arg("build")
+            .output()
+            .with_context(|| format!("Failed to build {name}"))?;
+        if !build_output.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to build {}: {}",
+                name,
+                String::from_utf8_lossy(&build_output.stderr)
+            ));
+        }
+    }
+
+    // Start the server on a free port
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+    let url = format!("http://{addr}/mcp");
+    let mut server_process = AsyncCommand::new(
+        scaffold_path
+            .join(server_name)
+            .join("target/debug")
+            .join(server_name),
+    )
+    .args([
+        "--transport",
+        "streamable-http",
+        "--sse-addr",
+        &addr.to_string(),
+    ])
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .kill_on_drop(true)
+    .spawn()
+    .context("Failed to start server in Streamable HTTP mode")?;
+
+    let http = reqwest::Client::new();
+    let initialize = serde_json::json!({
+        "jsonrpc": "2.0", "id": 1, "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": {"name": "e2e", "version": "0"}
+        }
+    });
+    let mut response = None;
+    for _ in 0..50 {
+        match http
+            .post(&url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(&initialize)
+            .send()
+            .await
+        {
+            Ok(r) => {
+                response = Some(r);
+                break;
+            }
+            Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+        }
+    }
+    let response = response.context("Server did not accept connections")?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+    // Initializing opens a session the client must name in later requests
+    let session = response
+        .headers()
+        .get("mcp-session-id")
+        .context("initialize response lacks Mcp-Session-Id")?
+        .to_str()?
+        .to_string();
+    let body = response.text().await?;
+    assert!(body.contains("\"serverInfo\""), "{body}");
+    info!("✅ Session {} opened", session);
+
+    let notification = http
+        .post(&url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Mcp-Session-Id", &session)
+        .json(&serde_json::json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+        .send()
+        .await?;
+    assert_eq!(notification.status(), reqwest::StatusCode::ACCEPTED);
+
+    // Responses come on streams whose events can be resumed by ID
+    let tools = http
+        .post(&url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Mcp-Session-Id", &session)
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+        .send()
+        .await?
+        .text()
+        .await?;
+    assert!(tools.contains("\"tools\""), "{tools}");
+    assert!(tools.lines().any(|line| line.starts_with("id:")), "{tools}");
+
+    // Without a session, or with a closed one, requests are refused
+    let anonymous = http
+        .post(&url)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 3, "method": "tools/list"}))
+        .send()
+        .await?;
+    assert!(anonymous.status().is_client_error());
+    let deleted = http
+        .delete(&url)
+        .header("Mcp-Session-Id", &session)
+        .send()
+        .await?;
+    assert!(deleted.status().is_success());
+    let closed = http
+        .get(&url)
+        .header("Accept", "text/event-stream")
+        .header("Mcp-Session-Id", &session)
+        .send()
+        .await?;
+    assert!(closed.status().is_client_error());
+    info!("✅ Session handling works");
+
+    // The generated client connects over the same transport
+    let mut client_process = AsyncCommand::new(
+        scaffold_path
+            .join(client_name)
+            .join("target/debug")
+            .join(client_name),
+    )
+    .args([
+        "--transport",
+        "streamable-http",
+        "--url",
+        &url,
+        "--timeout",
+        "10",
+    ])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .kill_on_drop(true)
+    .spawn()
+    .context("Failed to spawn Streamable HTTP client process")?;
+
+    let stdin = client_process
+        .stdin
+        .as_mut()
+        .context("Failed to get stdin")?;
+    let mut writer = BufWriter::new(stdin);
+    writer.write_all(b"status\n").await?;
+    writer.flush().await?;
+
+    let stdout = client_process
+        .stdout
+        .as_mut()
+        .context("Failed to get stdout")?;
+    let mut reader = BufReader::new(stdout);
+    let mut line = String::new();
+    let mut connected = false;
+    while timeout(Duration::from_secs(20), reader.read_line(&mut line))
+        .await
+        .is_ok_and(|read| read.is_ok_and(|n| n > 0))
+    {
+        if line.contains("Connected: true") {
+            connected = true;
+            break;
+        }
+        line.clear();
+    }
+
+    let _ = client_process.kill().await;
+    let _ = server_process.kill().await;
+    cleanup_project_databases(client_name)?;
+
+    assert!(connected, "Client did not report a connection");
+    info!("✅ Streamable HTTP transport test completed successfully");
+    Ok(())
+}